@@ -1,6 +1,8 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
+};
 
 mod test;
 
@@ -41,6 +43,16 @@ pub struct AccessPermission {
     pub expires_at: u64, // 0 means no expiration
 }
 
+/// --------------------
+/// Doctor Registry
+/// --------------------
+/// Doctor identity lives in the doctor registry; `EntityType::Doctor` entities
+/// are resolved against it
+#[contractclient(name = "DoctorRegistryClient")]
+pub trait DoctorRegistryInterface {
+    fn is_active_doctor(env: Env, wallet: Address) -> bool;
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
@@ -50,6 +62,7 @@ pub enum DataKey {
     Entity(Address),
     AccessList(Address),    // Entity -> Vec<AccessPermission>
     ResourceAccess(String), // Resource -> Vec<Address> (authorized parties)
+    DoctorRegistry,         // Address of the doctor registry contract
 }
 
 #[contract]
@@ -72,7 +85,46 @@ impl AccessControl {
             .publish((symbol_short!("init"), admin), symbol_short!("success"));
     }
 
-    /// Register a new entity in the system
+    /// Set the doctor registry used to resolve doctor entities (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `doctor_registry` - The address of the doctor registry contract
+    pub fn set_doctor_registry(env: Env, admin: Address, doctor_registry: Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if admin != stored_admin {
+            panic!("Only admin can set the doctor registry");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::DoctorRegistry, &doctor_registry);
+
+        env.events().publish(
+            (symbol_short!("set_dreg"), doctor_registry),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Check whether a wallet is an active doctor in the doctor registry
+    fn is_active_doctor(env: &Env, wallet: &Address) -> bool {
+        let registry: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DoctorRegistry)
+            .expect("Doctor registry not configured");
+        DoctorRegistryClient::new(env, &registry).is_active_doctor(wallet)
+    }
+
+    /// Register a new entity in the system. Doctors must already be registered
+    /// and active in the doctor registry.
     ///
     /// # Arguments
     /// * `wallet` - The wallet address of the entity
@@ -93,6 +145,10 @@ impl AccessControl {
             panic!("Entity already registered");
         }
 
+        if entity_type == EntityType::Doctor && !Self::is_active_doctor(&env, &wallet) {
+            panic!("Doctor not registered in doctor registry");
+        }
+
         let entity = EntityData {
             entity_type,
             name,
@@ -135,9 +191,17 @@ impl AccessControl {
         }
 
         // Verify grantee is a registered entity
-        let grantee_key = DataKey::Entity(grantee.clone());
-        if !env.storage().persistent().has(&grantee_key) {
-            panic!("Grantee not registered");
+        let grantee_entity: EntityData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Entity(grantee.clone()))
+            .expect("Grantee not registered");
+
+        // Doctors suspended, retired or deceased in the doctor registry cannot receive access
+        if grantee_entity.entity_type == EntityType::Doctor
+            && !Self::is_active_doctor(&env, &grantee)
+        {
+            panic!("Doctor is not active");
         }
 
         let permission = AccessPermission {
@@ -272,8 +336,23 @@ impl AccessControl {
     /// * `resource_id` - The identifier of the resource
     ///
     /// # Returns
-    /// `true` if the entity has valid (non-expired) access, `false` otherwise
+    /// `true` if the entity has valid (non-expired) access, `false` otherwise.
+    /// Doctors that are not active in the doctor registry have no access, nor
+    /// does any doctor while no doctor registry is configured.
     pub fn check_access(env: Env, entity: Address, resource_id: String) -> bool {
+        let entity_data: Option<EntityData> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Entity(entity.clone()));
+        if let Some(entity_data) = entity_data {
+            if entity_data.entity_type == EntityType::Doctor
+                && (!env.storage().persistent().has(&DataKey::DoctorRegistry)
+                    || !Self::is_active_doctor(&env, &entity))
+            {
+                return false;
+            }
+        }
+
         let access_key = DataKey::AccessList(entity);
         let access_list: Vec<AccessPermission> = env
             .storage()
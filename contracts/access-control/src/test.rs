@@ -1,7 +1,28 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+/// Registers a doctor registry with one doctor profile and points the access
+/// control contract at it
+fn register_canonical_doctor<'a>(
+    env: &'a Env,
+    client: &AccessControlClient<'a>,
+    admin: &Address,
+) -> (Address, doctor_registry::DoctorRegistryClient<'a>) {
+    let doctors_id = env.register(doctor_registry::DoctorRegistry, (admin.clone(),));
+    let doctors = doctor_registry::DoctorRegistryClient::new(env, &doctors_id);
+    client.set_doctor_registry(admin, &doctors_id);
+
+    let doctor = Address::generate(env);
+    doctors.create_doctor_profile(
+        &doctor,
+        &String::from_str(env, "Dr. Smith"),
+        &String::from_str(env, "General Medicine"),
+        &Address::generate(env),
+    );
+    (doctor, doctors)
+}
 
 #[test]
 fn test_initialize() {
@@ -85,7 +106,7 @@ fn test_grant_access() {
 
     // Register a hospital and a doctor
     let hospital = Address::generate(&env);
-    let doctor = Address::generate(&env);
+    let (doctor, _doctors) = register_canonical_doctor(&env, &client, &admin);
 
     client.register_entity(
         &hospital,
@@ -125,7 +146,7 @@ fn test_revoke_access() {
     client.initialize(&admin);
 
     let hospital = Address::generate(&env);
-    let doctor = Address::generate(&env);
+    let (doctor, _doctors) = register_canonical_doctor(&env, &client, &admin);
 
     client.register_entity(
         &hospital,
@@ -172,7 +193,7 @@ fn test_check_access_expired() {
     client.initialize(&admin);
 
     let hospital = Address::generate(&env);
-    let doctor = Address::generate(&env);
+    let (doctor, _doctors) = register_canonical_doctor(&env, &client, &admin);
 
     client.register_entity(
         &hospital,
@@ -214,7 +235,7 @@ fn test_get_entity_permissions() {
     client.initialize(&admin);
 
     let hospital = Address::generate(&env);
-    let doctor = Address::generate(&env);
+    let (doctor, _doctors) = register_canonical_doctor(&env, &client, &admin);
 
     client.register_entity(
         &hospital,
@@ -321,3 +342,100 @@ fn test_update_entity() {
     let entity = client.get_entity(&hospital);
     assert_eq!(entity.metadata, new_metadata);
 }
+
+#[test]
+#[should_panic(expected = "Doctor not registered in doctor registry")]
+fn test_register_unknown_doctor_should_fail() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_canonical_doctor(&env, &client, &admin);
+
+    client.register_entity(
+        &Address::generate(&env),
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Nobody"),
+        &String::from_str(&env, "metadata"),
+    );
+}
+
+#[test]
+fn test_suspended_doctor_loses_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let (doctor, doctors) = register_canonical_doctor(&env, &client, &admin);
+
+    let hospital = Address::generate(&env);
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    let resource_id = String::from_str(&env, "patient-123-records");
+    client.grant_access(&hospital, &doctor, &resource_id, &0);
+    assert!(client.check_access(&doctor, &resource_id));
+
+    doctors.suspend_doctor(&admin, &doctor, &String::from_str(&env, "License review"));
+    assert!(!client.check_access(&doctor, &resource_id));
+    assert!(client
+        .try_grant_access(&hospital, &doctor, &String::from_str(&env, "other"), &0)
+        .is_err());
+}
+
+#[test]
+fn test_doctor_without_registry_has_no_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    // A doctor entity and grant stored before the doctor registry was configured
+    let doctor = Address::generate(&env);
+    let resource_id = String::from_str(&env, "patient-123-records");
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::Entity(doctor.clone()),
+            &EntityData {
+                entity_type: EntityType::Doctor,
+                name: String::from_str(&env, "Dr. Smith"),
+                metadata: String::from_str(&env, "metadata"),
+                active: true,
+            },
+        );
+        env.storage().persistent().set(
+            &DataKey::AccessList(doctor.clone()),
+            &vec![
+                &env,
+                AccessPermission {
+                    resource_id: resource_id.clone(),
+                    granted_by: Address::generate(&env),
+                    granted_at: 0,
+                    expires_at: 0,
+                },
+            ],
+        );
+    });
+
+    assert!(!client.check_access(&doctor, &resource_id));
+}
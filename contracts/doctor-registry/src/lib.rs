@@ -1,17 +1,174 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN,
+    Env, String, Vec,
+};
 
 /// --------------------
 /// Doctor Structures
 /// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DoctorStatus {
+    Active,
+    Suspended, // Set by the admin or a licensing authority, can be reinstated
+    Retired,   // Set by the doctor, can be reinstated by the admin or a licensing authority
+    Deceased,  // Final
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DoctorProfileData {
     pub name: String,
     pub specialization: String,
-    pub institution_wallet: Address,
+    pub specialty_code: String, // Normalized from `specialization`, e.g. "PEDIATRIC_NEUROLOGY"
     pub metadata: String,
+    pub accepting_new_patients: bool,
+    pub languages: Vec<String>, // Languages spoken, e.g. "en", "es"
+    pub status: DoctorStatus,
+    pub status_reason: String,
+    pub status_updated_at: u64,
+}
+
+/// --------------------
+/// Affiliation Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Privilege {
+    Admitting,
+    Surgical,
+    Consulting,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AffiliationStatus {
+    Pending,    // Requested by the doctor, awaiting the institution
+    Confirmed,  // Confirmed by the institution
+    Terminated, // Ended by the institution
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Affiliation {
+    pub institution: Address,
+    pub privileges: Vec<Privilege>, // Granted by the institution on confirmation
+    pub start_date: u64,
+    pub end_date: Option<u64>, // None for open-ended affiliations
+    pub status: AffiliationStatus,
+    pub requested_at: u64,
+    pub updated_at: u64,
+}
+
+/// --------------------
+/// Credential Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MedicalLicense {
+    pub license_number: String,
+    pub jurisdiction: String, // e.g. "US-CA", "GB"
+    pub expires_at: u64,
+    pub issuer: Address,
+    pub attested_at: u64,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardCertification {
+    pub board: String,     // e.g. "ABIM"
+    pub specialty: String, // e.g. "Cardiovascular Disease"
+    pub certificate_number: String,
+    pub expires_at: u64,
+    pub issuer: Address,
+    pub attested_at: u64,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ControlledSubstanceRegistration {
+    pub registration_number: String, // DEA number or local equivalent
+    pub jurisdiction: String,
+    pub schedules: Vec<u32>, // Controlled substance schedules covered, e.g. [2, 3, 4, 5]
+    pub expires_at: u64,
+    pub issuer: Address,
+    pub attested_at: u64,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorCredentials {
+    pub licenses: Vec<MedicalLicense>, // At most one per jurisdiction
+    pub board_certifications: Vec<BoardCertification>, // At most one per board and specialty
+    pub dea_registrations: Vec<ControlledSubstanceRegistration>, // At most one per jurisdiction
+}
+
+/// --------------------
+/// Legacy Import
+/// --------------------
+/// Doctor record as stored by the patient registry's `MedicalRegistry`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyDoctorData {
+    pub name: String,
+    pub specialization: String,
+    pub certificate_hash: Bytes,
+    pub verified: bool,
+}
+
+#[contractclient(name = "MedicalRegistryClient")]
+pub trait MedicalRegistryInterface {
+    fn get_doctor(env: Env, wallet: Address) -> LegacyDoctorData;
+}
+
+/// --------------------
+/// Review Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Review {
+    pub review_id: u64,
+    pub doctor: Address,
+    pub patient: Address,
+    pub visit_source: Address, // Contract that recorded the visit
+    pub visit_id: u64,
+    pub rating: u32, // 1 to 5
+    pub content_hash: BytesN<32>,
+    pub created_at: u64,
+    pub response_hash: Option<BytesN<32>>, // The doctor's single response, if any
+    pub responded_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingSummary {
+    pub review_count: u32,
+    pub rating_total: u64,
+    pub average_rating: u32, // Scaled by 100, e.g. 450 for 4.5 stars
+}
+
+/// A page of filtered directory results
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorPage {
+    pub doctors: Vec<Address>,
+    pub next_cursor: Option<u32>, // Index position to resume from, None once exhausted
+}
+
+/// Interface of a contract that records appointments or visits, e.g. telemedicine
+#[contractclient(name = "VisitSourceClient")]
+pub trait VisitSourceInterface {
+    fn is_completed_visit(
+        env: Env,
+        visit_id: u64,
+        patient_id: Address,
+        provider_id: Address,
+    ) -> bool;
 }
 
 /// --------------------
@@ -20,20 +177,40 @@ pub struct DoctorProfileData {
 #[contracttype]
 pub enum DataKey {
     Doctor(Address),
+    Admin,
+    CredentialIssuer(Address), // Licensing authority or certifying board -> bool
+    Credentials(Address),      // Doctor -> DoctorCredentials
+    Affiliations(Address),     // Doctor -> Vec<Affiliation>, at most one per institution
+    SpecialtyDoctors(String),  // Specialty code -> Vec<Address>
+    InstitutionDoctors(Address), // Institution -> Vec<Address> with confirmed affiliations
+    LegacyImportClosed,        // Set once the MedicalRegistry import is finished
+    VisitSource(Address),      // Appointment/visit contract trusted for reviews -> bool
+    ReviewCount,               // Number of reviews submitted
+    Review(u64),               // Review id -> Review
+    DoctorReviews(Address),    // Doctor -> Vec<u64> of review ids
+    VisitReview(Address, u64), // (Visit source, Visit id) -> review id
+    Rating(Address),           // Doctor -> RatingSummary
 }
 
+/// Upper bound on the number of entries returned by a single directory page
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Longest specialization accepted, in bytes
+const MAX_SPECIALTY_LEN: usize = 64;
+
 #[contract]
 pub struct DoctorRegistry;
 
 #[contractimpl]
 impl DoctorRegistry {
-    /// Create a new doctor profile with basic information and institution association
+    /// Create a new doctor profile with basic information. The affiliation with
+    /// the institution stays pending until the institution confirms it.
     ///
     /// # Arguments
     /// * `wallet` - The wallet address of the doctor
     /// * `name` - The name of the doctor
     /// * `specialization` - The area of specialization
-    /// * `institution_wallet` - The wallet address of the associated hospital/clinic
+    /// * `institution_wallet` - The wallet address of the hospital/clinic to request affiliation with
     pub fn create_doctor_profile(
         env: Env,
         wallet: Address,
@@ -48,17 +225,43 @@ impl DoctorRegistry {
             panic!("Doctor profile already exists");
         }
 
+        let specialty_code = Self::normalize_specialty(&env, &specialization);
+        Self::store_new_profile(&env, &wallet, name, specialization, specialty_code);
+        Self::add_affiliation_request(
+            &env,
+            &wallet,
+            institution_wallet,
+            env.ledger().timestamp(),
+            None,
+        );
+
+        env.events()
+            .publish((symbol_short!("crt_doc"), wallet), symbol_short!("success"));
+    }
+
+    fn store_new_profile(
+        env: &Env,
+        wallet: &Address,
+        name: String,
+        specialization: String,
+        specialty_code: String,
+    ) {
         let doctor_profile = DoctorProfileData {
             name,
             specialization,
-            institution_wallet,
-            metadata: String::from_str(&env, ""),
+            specialty_code: specialty_code.clone(),
+            metadata: String::from_str(env, ""),
+            accepting_new_patients: true,
+            languages: Vec::new(env),
+            status: DoctorStatus::Active,
+            status_reason: String::from_str(env, ""),
+            status_updated_at: env.ledger().timestamp(),
         };
 
-        env.storage().persistent().set(&key, &doctor_profile);
-
-        env.events()
-            .publish((symbol_short!("crt_doc"), wallet), symbol_short!("success"));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Doctor(wallet.clone()), &doctor_profile);
+        Self::add_to_index(env, &DataKey::SpecialtyDoctors(specialty_code), wallet);
     }
 
     /// Update doctor profile specialization and metadata
@@ -82,7 +285,24 @@ impl DoctorRegistry {
             .get(&key)
             .expect("Doctor profile not found");
 
+        let specialty_code = Self::normalize_specialty(&env, &specialization);
+        if specialty_code != doctor_profile.specialty_code
+            && doctor_profile.status == DoctorStatus::Active
+        {
+            Self::remove_from_index(
+                &env,
+                &DataKey::SpecialtyDoctors(doctor_profile.specialty_code.clone()),
+                &wallet,
+            );
+            Self::add_to_index(
+                &env,
+                &DataKey::SpecialtyDoctors(specialty_code.clone()),
+                &wallet,
+            );
+        }
+
         doctor_profile.specialization = specialization;
+        doctor_profile.specialty_code = specialty_code;
         doctor_profile.metadata = metadata;
         env.storage().persistent().set(&key, &doctor_profile);
 
@@ -104,6 +324,1271 @@ impl DoctorRegistry {
             .get(&key)
             .expect("Doctor profile not found")
     }
+
+    // =====================================================
+    //                  CREDENTIAL ATTESTATIONS
+    // =====================================================
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *admin != stored_admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+
+    fn require_issuer(env: &Env, issuer: &Address) {
+        issuer.require_auth();
+
+        if !Self::is_credential_issuer(env.clone(), issuer.clone()) {
+            panic!("Issuer is not a credential issuer");
+        }
+    }
+
+    fn assert_doctor_exists(env: &Env, wallet: &Address) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Doctor(wallet.clone()))
+        {
+            panic!("Doctor profile not found");
+        }
+    }
+
+    fn save_credentials(env: &Env, wallet: &Address, credentials: &DoctorCredentials) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Credentials(wallet.clone()), credentials);
+    }
+
+    /// Internal helper: panics with the reason an issuer has no unrevoked
+    /// credential to revoke, given the revoked flags of the entries for the key
+    fn revoke_failure(revoked: impl Iterator<Item = bool>, not_found: &'static str) -> ! {
+        let mut found = false;
+        for revoked in revoked {
+            if !revoked {
+                panic!("Only the issuer can revoke this credential");
+            }
+            found = true;
+        }
+        if found {
+            panic!("Credential already revoked");
+        }
+        panic!("{}", not_found)
+    }
+
+    /// Internal helper: an attestation counts only while unrevoked, unexpired and
+    /// issued by an address that is still a credential issuer
+    fn is_current(env: &Env, issuer: &Address, expires_at: u64, revoked: bool) -> bool {
+        !revoked
+            && expires_at > env.ledger().timestamp()
+            && Self::is_credential_issuer(env.clone(), issuer.clone())
+    }
+
+    /// Set the admin who governs credential issuers when the contract is deployed,
+    /// so that no later caller can claim the admin role
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address for the contract
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        env.events()
+            .publish((symbol_short!("init"), admin), symbol_short!("success"));
+    }
+
+    /// Add a licensing authority or certifying board allowed to attest credentials (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `issuer` - The wallet address of the issuer
+    pub fn add_credential_issuer(env: Env, admin: Address, issuer: Address) {
+        Self::require_admin(&env, &admin);
+
+        let key = DataKey::CredentialIssuer(issuer.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("Credential issuer already registered");
+        }
+        env.storage().persistent().set(&key, &true);
+
+        env.events()
+            .publish((symbol_short!("add_iss"), issuer), symbol_short!("success"));
+    }
+
+    /// Remove a credential issuer (admin only). Credentials it attested stop
+    /// counting as valid.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `issuer` - The wallet address of the issuer
+    pub fn remove_credential_issuer(env: Env, admin: Address, issuer: Address) {
+        Self::require_admin(&env, &admin);
+
+        let key = DataKey::CredentialIssuer(issuer.clone());
+        if !env.storage().persistent().has(&key) {
+            panic!("Credential issuer not found");
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("rm_iss"), issuer), symbol_short!("success"));
+    }
+
+    /// Check whether an address is a registered credential issuer
+    pub fn is_credential_issuer(env: Env, issuer: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::CredentialIssuer(issuer))
+    }
+
+    /// Retrieve all credentials (including expired and revoked) attested for a doctor
+    pub fn get_doctor_credentials(env: Env, wallet: Address) -> DoctorCredentials {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Credentials(wallet))
+            .unwrap_or(DoctorCredentials {
+                licenses: Vec::new(&env),
+                board_certifications: Vec::new(&env),
+                dea_registrations: Vec::new(&env),
+            })
+    }
+
+    /// Attest a medical license, replacing the issuer's own unrevoked license for
+    /// the same jurisdiction. Licenses from other issuers and revoked licenses are
+    /// kept, and the new license is added alongside them.
+    ///
+    /// # Arguments
+    /// * `issuer` - The licensing authority attesting the license
+    /// * `wallet` - The wallet address of the doctor
+    /// * `license_number` - The license number
+    /// * `jurisdiction` - The issuing jurisdiction
+    /// * `expires_at` - Expiration timestamp of the license
+    pub fn attest_license(
+        env: Env,
+        issuer: Address,
+        wallet: Address,
+        license_number: String,
+        jurisdiction: String,
+        expires_at: u64,
+    ) {
+        Self::require_issuer(&env, &issuer);
+        Self::assert_doctor_exists(&env, &wallet);
+
+        let attested_at = env.ledger().timestamp();
+        if expires_at <= attested_at {
+            panic!("Invalid expiration");
+        }
+
+        let mut credentials = Self::get_doctor_credentials(env.clone(), wallet.clone());
+        let license = MedicalLicense {
+            license_number,
+            jurisdiction: jurisdiction.clone(),
+            expires_at,
+            issuer: issuer.clone(),
+            attested_at,
+            revoked: false,
+        };
+        match credentials
+            .licenses
+            .iter()
+            .position(|l| l.jurisdiction == jurisdiction && l.issuer == issuer && !l.revoked)
+        {
+            Some(i) => credentials.licenses.set(i as u32, license),
+            None => credentials.licenses.push_back(license),
+        }
+        Self::save_credentials(&env, &wallet, &credentials);
+
+        env.events()
+            .publish((symbol_short!("lic_att"), wallet, issuer), jurisdiction);
+    }
+
+    /// Revoke a medical license. Only the issuer that attested it may revoke it.
+    ///
+    /// # Arguments
+    /// * `issuer` - The licensing authority that attested the license
+    /// * `wallet` - The wallet address of the doctor
+    /// * `jurisdiction` - The jurisdiction of the license to revoke
+    pub fn revoke_license(env: Env, issuer: Address, wallet: Address, jurisdiction: String) {
+        issuer.require_auth();
+
+        let mut credentials = Self::get_doctor_credentials(env.clone(), wallet.clone());
+        let i = match credentials
+            .licenses
+            .iter()
+            .position(|l| l.jurisdiction == jurisdiction && l.issuer == issuer && !l.revoked)
+        {
+            Some(i) => i as u32,
+            None => Self::revoke_failure(
+                credentials
+                    .licenses
+                    .iter()
+                    .filter(|l| l.jurisdiction == jurisdiction)
+                    .map(|l| l.revoked),
+                "License not found",
+            ),
+        };
+        let mut license = credentials.licenses.get_unchecked(i);
+        license.revoked = true;
+        credentials.licenses.set(i, license);
+        Self::save_credentials(&env, &wallet, &credentials);
+
+        env.events()
+            .publish((symbol_short!("lic_rev"), wallet, issuer), jurisdiction);
+    }
+
+    /// Attest a board certification, replacing the issuer's own unrevoked
+    /// certification from the same board in the same specialty. Other entries
+    /// are kept, as for `attest_license`.
+    ///
+    /// # Arguments
+    /// * `issuer` - The certifying board attesting the certification
+    /// * `wallet` - The wallet address of the doctor
+    /// * `board` - The certifying board's name
+    /// * `specialty` - The certified specialty
+    /// * `certificate_number` - The certificate number
+    /// * `expires_at` - Expiration timestamp of the certification
+    pub fn attest_board_certification(
+        env: Env,
+        issuer: Address,
+        wallet: Address,
+        board: String,
+        specialty: String,
+        certificate_number: String,
+        expires_at: u64,
+    ) {
+        Self::require_issuer(&env, &issuer);
+        Self::assert_doctor_exists(&env, &wallet);
+
+        let attested_at = env.ledger().timestamp();
+        if expires_at <= attested_at {
+            panic!("Invalid expiration");
+        }
+
+        let mut credentials = Self::get_doctor_credentials(env.clone(), wallet.clone());
+        let certification = BoardCertification {
+            board: board.clone(),
+            specialty: specialty.clone(),
+            certificate_number,
+            expires_at,
+            issuer: issuer.clone(),
+            attested_at,
+            revoked: false,
+        };
+        match credentials.board_certifications.iter().position(|c| {
+            c.board == board && c.specialty == specialty && c.issuer == issuer && !c.revoked
+        }) {
+            Some(i) => credentials
+                .board_certifications
+                .set(i as u32, certification),
+            None => credentials.board_certifications.push_back(certification),
+        }
+        Self::save_credentials(&env, &wallet, &credentials);
+
+        env.events()
+            .publish((symbol_short!("cert_att"), wallet, issuer), specialty);
+    }
+
+    /// Revoke a board certification. Only the issuer that attested it may revoke it.
+    ///
+    /// # Arguments
+    /// * `issuer` - The certifying board that attested the certification
+    /// * `wallet` - The wallet address of the doctor
+    /// * `board` - The certifying board's name
+    /// * `specialty` - The certified specialty
+    pub fn revoke_board_certification(
+        env: Env,
+        issuer: Address,
+        wallet: Address,
+        board: String,
+        specialty: String,
+    ) {
+        issuer.require_auth();
+
+        let mut credentials = Self::get_doctor_credentials(env.clone(), wallet.clone());
+        let i = match credentials.board_certifications.iter().position(|c| {
+            c.board == board && c.specialty == specialty && c.issuer == issuer && !c.revoked
+        }) {
+            Some(i) => i as u32,
+            None => Self::revoke_failure(
+                credentials
+                    .board_certifications
+                    .iter()
+                    .filter(|c| c.board == board && c.specialty == specialty)
+                    .map(|c| c.revoked),
+                "Board certification not found",
+            ),
+        };
+        let mut certification = credentials.board_certifications.get_unchecked(i);
+        certification.revoked = true;
+        credentials.board_certifications.set(i, certification);
+        Self::save_credentials(&env, &wallet, &credentials);
+
+        env.events()
+            .publish((symbol_short!("cert_rev"), wallet, issuer), specialty);
+    }
+
+    /// Attest a DEA or other controlled-substance registration, replacing the
+    /// issuer's own unrevoked registration for the same jurisdiction. Other
+    /// entries are kept, as for `attest_license`.
+    ///
+    /// # Arguments
+    /// * `issuer` - The authority attesting the registration
+    /// * `wallet` - The wallet address of the doctor
+    /// * `registration_number` - The registration number
+    /// * `jurisdiction` - The jurisdiction the registration applies to
+    /// * `schedules` - The controlled substance schedules covered
+    /// * `expires_at` - Expiration timestamp of the registration
+    pub fn attest_dea_registration(
+        env: Env,
+        issuer: Address,
+        wallet: Address,
+        registration_number: String,
+        jurisdiction: String,
+        schedules: Vec<u32>,
+        expires_at: u64,
+    ) {
+        Self::require_issuer(&env, &issuer);
+        Self::assert_doctor_exists(&env, &wallet);
+
+        let attested_at = env.ledger().timestamp();
+        if expires_at <= attested_at {
+            panic!("Invalid expiration");
+        }
+        if schedules.is_empty() {
+            panic!("At least one schedule required");
+        }
+
+        let mut credentials = Self::get_doctor_credentials(env.clone(), wallet.clone());
+        let registration = ControlledSubstanceRegistration {
+            registration_number,
+            jurisdiction: jurisdiction.clone(),
+            schedules,
+            expires_at,
+            issuer: issuer.clone(),
+            attested_at,
+            revoked: false,
+        };
+        match credentials
+            .dea_registrations
+            .iter()
+            .position(|r| r.jurisdiction == jurisdiction && r.issuer == issuer && !r.revoked)
+        {
+            Some(i) => credentials.dea_registrations.set(i as u32, registration),
+            None => credentials.dea_registrations.push_back(registration),
+        }
+        Self::save_credentials(&env, &wallet, &credentials);
+
+        env.events()
+            .publish((symbol_short!("dea_att"), wallet, issuer), jurisdiction);
+    }
+
+    /// Revoke a controlled-substance registration. Only the issuer that attested
+    /// it may revoke it.
+    ///
+    /// # Arguments
+    /// * `issuer` - The authority that attested the registration
+    /// * `wallet` - The wallet address of the doctor
+    /// * `jurisdiction` - The jurisdiction of the registration to revoke
+    pub fn revoke_dea_registration(
+        env: Env,
+        issuer: Address,
+        wallet: Address,
+        jurisdiction: String,
+    ) {
+        issuer.require_auth();
+
+        let mut credentials = Self::get_doctor_credentials(env.clone(), wallet.clone());
+        let i = match credentials
+            .dea_registrations
+            .iter()
+            .position(|r| r.jurisdiction == jurisdiction && r.issuer == issuer && !r.revoked)
+        {
+            Some(i) => i as u32,
+            None => Self::revoke_failure(
+                credentials
+                    .dea_registrations
+                    .iter()
+                    .filter(|r| r.jurisdiction == jurisdiction)
+                    .map(|r| r.revoked),
+                "Registration not found",
+            ),
+        };
+        let mut registration = credentials.dea_registrations.get_unchecked(i);
+        registration.revoked = true;
+        credentials.dea_registrations.set(i, registration);
+        Self::save_credentials(&env, &wallet, &credentials);
+
+        env.events()
+            .publish((symbol_short!("dea_rev"), wallet, issuer), jurisdiction);
+    }
+
+    /// Check whether a doctor holds a valid medical license in a jurisdiction
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    /// * `jurisdiction` - The jurisdiction to check
+    ///
+    /// # Returns
+    /// `true` if an unrevoked, unexpired license for that jurisdiction exists
+    /// from an issuer that is still registered, `false` otherwise
+    pub fn has_valid_license(env: Env, wallet: Address, jurisdiction: String) -> bool {
+        let credentials = Self::get_doctor_credentials(env.clone(), wallet);
+        credentials.licenses.iter().any(|l| {
+            l.jurisdiction == jurisdiction
+                && Self::is_current(&env, &l.issuer, l.expires_at, l.revoked)
+        })
+    }
+
+    /// Check whether a doctor holds a valid board certification in a specialty
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    /// * `specialty` - The specialty to check, from any board
+    pub fn has_valid_board_certification(env: Env, wallet: Address, specialty: String) -> bool {
+        let credentials = Self::get_doctor_credentials(env.clone(), wallet);
+        credentials.board_certifications.iter().any(|c| {
+            c.specialty == specialty && Self::is_current(&env, &c.issuer, c.expires_at, c.revoked)
+        })
+    }
+
+    /// Check whether a doctor may prescribe a controlled substance schedule in a jurisdiction
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    /// * `jurisdiction` - The jurisdiction to check
+    /// * `schedule` - The controlled substance schedule, e.g. 2
+    pub fn has_valid_dea_registration(
+        env: Env,
+        wallet: Address,
+        jurisdiction: String,
+        schedule: u32,
+    ) -> bool {
+        let credentials = Self::get_doctor_credentials(env.clone(), wallet);
+        credentials.dea_registrations.iter().any(|r| {
+            r.jurisdiction == jurisdiction
+                && r.schedules.contains(schedule)
+                && Self::is_current(&env, &r.issuer, r.expires_at, r.revoked)
+        })
+    }
+
+    // =====================================================
+    //                     AFFILIATIONS
+    // =====================================================
+
+    fn load_affiliations(env: &Env, wallet: &Address) -> Vec<Affiliation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Affiliations(wallet.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn find_affiliation(affiliations: &Vec<Affiliation>, institution: &Address) -> Option<u32> {
+        affiliations
+            .iter()
+            .position(|a| a.institution == *institution)
+            .map(|i| i as u32)
+    }
+
+    fn add_affiliation_request(
+        env: &Env,
+        wallet: &Address,
+        institution: Address,
+        start_date: u64,
+        end_date: Option<u64>,
+    ) {
+        if let Some(end_date) = end_date {
+            if end_date <= start_date {
+                panic!("Invalid affiliation dates");
+            }
+        }
+
+        let mut affiliations = Self::load_affiliations(env, wallet);
+        let now = env.ledger().timestamp();
+        let affiliation = Affiliation {
+            institution: institution.clone(),
+            privileges: Vec::new(env),
+            start_date,
+            end_date,
+            status: AffiliationStatus::Pending,
+            requested_at: now,
+            updated_at: now,
+        };
+        match Self::find_affiliation(&affiliations, &institution) {
+            Some(i) => {
+                if affiliations.get_unchecked(i).status != AffiliationStatus::Terminated {
+                    panic!("Affiliation already exists");
+                }
+                affiliations.set(i, affiliation);
+            }
+            None => affiliations.push_back(affiliation),
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Affiliations(wallet.clone()), &affiliations);
+
+        env.events().publish(
+            (symbol_short!("aff_req"), wallet.clone(), institution),
+            symbol_short!("pending"),
+        );
+    }
+
+    /// Request an affiliation with an institution, pending its confirmation.
+    /// A terminated affiliation with the same institution may be requested again.
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    /// * `institution` - The wallet address of the hospital/clinic
+    /// * `start_date` - Timestamp the affiliation starts
+    /// * `end_date` - Timestamp the affiliation ends, if fixed-term
+    pub fn request_affiliation(
+        env: Env,
+        wallet: Address,
+        institution: Address,
+        start_date: u64,
+        end_date: Option<u64>,
+    ) {
+        wallet.require_auth();
+        Self::assert_doctor_exists(&env, &wallet);
+        Self::add_affiliation_request(&env, &wallet, institution, start_date, end_date);
+    }
+
+    /// Confirm a pending affiliation and grant privileges (institution only)
+    ///
+    /// # Arguments
+    /// * `institution` - The wallet address of the hospital/clinic
+    /// * `wallet` - The wallet address of the doctor
+    /// * `privileges` - The privileges granted at the institution
+    pub fn confirm_affiliation(
+        env: Env,
+        institution: Address,
+        wallet: Address,
+        privileges: Vec<Privilege>,
+    ) {
+        institution.require_auth();
+
+        if privileges.is_empty() {
+            panic!("At least one privilege required");
+        }
+
+        let mut affiliations = Self::load_affiliations(&env, &wallet);
+        let i = Self::find_affiliation(&affiliations, &institution).expect("Affiliation not found");
+        let mut affiliation = affiliations.get_unchecked(i);
+        if affiliation.status != AffiliationStatus::Pending {
+            panic!("Affiliation is not pending");
+        }
+
+        affiliation.privileges = privileges;
+        affiliation.status = AffiliationStatus::Confirmed;
+        affiliation.updated_at = env.ledger().timestamp();
+        affiliations.set(i, affiliation);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Affiliations(wallet.clone()), &affiliations);
+        Self::add_to_index(
+            &env,
+            &DataKey::InstitutionDoctors(institution.clone()),
+            &wallet,
+        );
+
+        env.events().publish(
+            (symbol_short!("aff_conf"), wallet, institution),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Terminate a pending or confirmed affiliation (institution only)
+    ///
+    /// # Arguments
+    /// * `institution` - The wallet address of the hospital/clinic
+    /// * `wallet` - The wallet address of the doctor
+    pub fn terminate_affiliation(env: Env, institution: Address, wallet: Address) {
+        institution.require_auth();
+
+        let mut affiliations = Self::load_affiliations(&env, &wallet);
+        let i = Self::find_affiliation(&affiliations, &institution).expect("Affiliation not found");
+        let mut affiliation = affiliations.get_unchecked(i);
+        if affiliation.status == AffiliationStatus::Terminated {
+            panic!("Affiliation already terminated");
+        }
+
+        affiliation.status = AffiliationStatus::Terminated;
+        affiliation.updated_at = env.ledger().timestamp();
+        affiliations.set(i, affiliation);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Affiliations(wallet.clone()), &affiliations);
+        Self::remove_from_index(
+            &env,
+            &DataKey::InstitutionDoctors(institution.clone()),
+            &wallet,
+        );
+
+        env.events().publish(
+            (symbol_short!("aff_term"), wallet, institution),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Retrieve a doctor's affiliation with an institution in any status
+    pub fn get_affiliation(env: Env, wallet: Address, institution: Address) -> Affiliation {
+        let affiliations = Self::load_affiliations(&env, &wallet);
+        let i = Self::find_affiliation(&affiliations, &institution).expect("Affiliation not found");
+        affiliations.get_unchecked(i)
+    }
+
+    /// Retrieve a doctor's confirmed affiliations that are currently in effect
+    pub fn get_affiliations(env: Env, wallet: Address) -> Vec<Affiliation> {
+        let now = env.ledger().timestamp();
+        let mut active = Vec::new(&env);
+        for affiliation in Self::load_affiliations(&env, &wallet).iter() {
+            if Self::is_active(&affiliation, now) {
+                active.push_back(affiliation);
+            }
+        }
+        active
+    }
+
+    /// Check whether a doctor holds a confirmed affiliation with an institution
+    /// that is currently in effect
+    pub fn is_affiliated(env: Env, wallet: Address, institution: Address) -> bool {
+        let affiliations = Self::load_affiliations(&env, &wallet);
+        match Self::find_affiliation(&affiliations, &institution) {
+            Some(i) => Self::is_active(&affiliations.get_unchecked(i), env.ledger().timestamp()),
+            None => false,
+        }
+    }
+
+    fn is_active(affiliation: &Affiliation, now: u64) -> bool {
+        affiliation.status == AffiliationStatus::Confirmed
+            && affiliation.start_date <= now
+            && affiliation.end_date.is_none_or(|end| end > now)
+    }
+
+    // =====================================================
+    //                      DIRECTORY
+    // =====================================================
+
+    fn add_to_index(env: &Env, key: &DataKey, wallet: &Address) {
+        let mut wallets: Vec<Address> =
+            env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        if !wallets.contains(wallet) {
+            wallets.push_back(wallet.clone());
+            env.storage().persistent().set(key, &wallets);
+        }
+    }
+
+    fn remove_from_index(env: &Env, key: &DataKey, wallet: &Address) {
+        let mut wallets: Vec<Address> =
+            env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        if let Some(i) = wallets.first_index_of(wallet) {
+            wallets.remove(i);
+            env.storage().persistent().set(key, &wallets);
+        }
+    }
+
+    fn paginate(env: &Env, wallets: Vec<Address>, offset: u32, limit: u32) -> Vec<Address> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        if offset >= wallets.len() {
+            return Vec::new(env);
+        }
+        let end = offset.saturating_add(limit).min(wallets.len());
+        wallets.slice(offset..end)
+    }
+
+    /// Internal helper to collect index entries from `cursor` onwards that pass
+    /// `matches`, examining at most `limit` (capped at 50) entries, so a page
+    /// may be short or empty and still carry a cursor
+    fn scan_index(
+        env: &Env,
+        wallets: &Vec<Address>,
+        cursor: u32,
+        limit: u32,
+        matches: impl Fn(&Address) -> bool,
+    ) -> DoctorPage {
+        let mut budget = limit.min(MAX_PAGE_SIZE);
+        let mut page = DoctorPage {
+            doctors: Vec::new(env),
+            next_cursor: None,
+        };
+        for i in cursor..wallets.len() {
+            if budget == 0 {
+                page.next_cursor = Some(i);
+                break;
+            }
+            budget -= 1;
+            let wallet = wallets.get_unchecked(i);
+            if matches(&wallet) {
+                page.doctors.push_back(wallet);
+            }
+        }
+        page
+    }
+
+    /// Internal helper to turn a free-text specialization into an index code:
+    /// upper-cased ASCII letters and digits, with each run of other characters
+    /// collapsed to a single underscore ("Pediatric neurology" -> "PEDIATRIC_NEUROLOGY")
+    fn normalize_specialty(env: &Env, specialization: &String) -> String {
+        Self::specialty_code(env, specialization).expect("Invalid specialization")
+    }
+
+    fn specialty_code(env: &Env, specialization: &String) -> Option<String> {
+        let len = specialization.len() as usize;
+        if len > MAX_SPECIALTY_LEN {
+            return None;
+        }
+        let mut raw = [0u8; MAX_SPECIALTY_LEN];
+        specialization.copy_into_slice(&mut raw[..len]);
+
+        let mut code = [0u8; MAX_SPECIALTY_LEN];
+        let mut n = 0;
+        let mut separator = false;
+        for c in raw[..len].iter() {
+            if c.is_ascii_alphanumeric() {
+                if separator && n > 0 {
+                    code[n] = b'_';
+                    n += 1;
+                }
+                separator = false;
+                code[n] = c.to_ascii_uppercase();
+                n += 1;
+            } else {
+                separator = true;
+            }
+        }
+        if n == 0 {
+            return None;
+        }
+        Some(String::from_bytes(env, &code[..n]))
+    }
+
+    /// Update the directory listing shown to patients and referring providers
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    /// * `accepting_new_patients` - Whether the doctor is taking new patients
+    /// * `languages` - Languages spoken, e.g. "en", "es"
+    pub fn set_directory_listing(
+        env: Env,
+        wallet: Address,
+        accepting_new_patients: bool,
+        languages: Vec<String>,
+    ) {
+        wallet.require_auth();
+
+        let key = DataKey::Doctor(wallet.clone());
+        let mut doctor_profile: DoctorProfileData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Doctor profile not found");
+
+        doctor_profile.accepting_new_patients = accepting_new_patients;
+        doctor_profile.languages = languages;
+        env.storage().persistent().set(&key, &doctor_profile);
+
+        env.events()
+            .publish((symbol_short!("upd_list"), wallet), accepting_new_patients);
+    }
+
+    /// List active doctors by specialty
+    ///
+    /// # Arguments
+    /// * `specialty` - The specialty, normalized the same way as profile specializations
+    /// * `offset` - Number of doctors to skip
+    /// * `limit` - Maximum number of doctors to return (capped at 50)
+    pub fn get_doctors_by_specialty(
+        env: Env,
+        specialty: String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        let code = Self::normalize_specialty(&env, &specialty);
+        let wallets: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SpecialtyDoctors(code))
+            .unwrap_or(Vec::new(&env));
+        Self::paginate(&env, wallets, offset, limit)
+    }
+
+    /// List active doctors with a confirmed affiliation currently in effect at an institution
+    ///
+    /// # Arguments
+    /// * `institution` - The wallet address of the hospital/clinic
+    /// * `cursor` - Index position to start from: 0, or the previous page's `next_cursor`
+    /// * `limit` - Maximum number of index entries to examine (capped at 50)
+    pub fn get_doctors_by_institution(
+        env: Env,
+        institution: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> DoctorPage {
+        let affiliated: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::InstitutionDoctors(institution.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        Self::scan_index(&env, &affiliated, cursor, limit, |wallet| {
+            Self::is_affiliated(env.clone(), wallet.clone(), institution.clone())
+                && Self::is_active_doctor(env.clone(), wallet.clone())
+        })
+    }
+
+    /// Search active doctors in a specialty by directory listing filters
+    ///
+    /// # Arguments
+    /// * `specialty` - The specialty, normalized the same way as profile specializations
+    /// * `accepting_new_patients` - Only return doctors taking new patients
+    /// * `language` - Only return doctors who speak this language, if set
+    /// * `cursor` - Index position to start from: 0, or the previous page's `next_cursor`
+    /// * `limit` - Maximum number of index entries to examine (capped at 50)
+    pub fn search_doctors(
+        env: Env,
+        specialty: String,
+        accepting_new_patients: bool,
+        language: Option<String>,
+        cursor: u32,
+        limit: u32,
+    ) -> DoctorPage {
+        let code = Self::normalize_specialty(&env, &specialty);
+        let in_specialty: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SpecialtyDoctors(code))
+            .unwrap_or(Vec::new(&env));
+
+        Self::scan_index(&env, &in_specialty, cursor, limit, |wallet| {
+            let profile: DoctorProfileData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Doctor(wallet.clone()))
+                .expect("Doctor profile not found");
+            (!accepting_new_patients || profile.accepting_new_patients)
+                && language
+                    .as_ref()
+                    .is_none_or(|language| profile.languages.contains(language))
+        })
+    }
+
+    // =====================================================
+    //                    DOCTOR STATUS
+    // =====================================================
+
+    fn require_authority(env: &Env, authority: &Address) {
+        authority.require_auth();
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() != Some(authority)
+            && !Self::is_credential_issuer(env.clone(), authority.clone())
+        {
+            panic!("Only admin or a licensing authority can perform this action");
+        }
+    }
+
+    /// Internal helper to apply a status change. Doctors leave the specialty
+    /// directory while inactive and return to it when reinstated.
+    fn set_status(env: &Env, wallet: &Address, status: DoctorStatus, reason: String) {
+        let key = DataKey::Doctor(wallet.clone());
+        let mut doctor_profile: DoctorProfileData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Doctor profile not found");
+
+        let index = DataKey::SpecialtyDoctors(doctor_profile.specialty_code.clone());
+        if status == DoctorStatus::Active {
+            Self::add_to_index(env, &index, wallet);
+        } else {
+            Self::remove_from_index(env, &index, wallet);
+        }
+
+        doctor_profile.status = status;
+        doctor_profile.status_reason = reason.clone();
+        doctor_profile.status_updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&key, &doctor_profile);
+
+        env.events().publish(
+            (symbol_short!("doc_stat"), wallet.clone()),
+            (status, reason),
+        );
+    }
+
+    /// Retire from practice (doctor only)
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    pub fn retire_doctor(env: Env, wallet: Address) {
+        wallet.require_auth();
+
+        if Self::get_doctor_status(env.clone(), wallet.clone()) != DoctorStatus::Active {
+            panic!("Doctor is not active");
+        }
+        Self::set_status(
+            &env,
+            &wallet,
+            DoctorStatus::Retired,
+            String::from_str(&env, ""),
+        );
+    }
+
+    /// Suspend an active doctor (admin or licensing authority only)
+    ///
+    /// # Arguments
+    /// * `authority` - The admin or licensing authority
+    /// * `wallet` - The wallet address of the doctor
+    /// * `reason` - Why the doctor is suspended
+    pub fn suspend_doctor(env: Env, authority: Address, wallet: Address, reason: String) {
+        Self::require_authority(&env, &authority);
+
+        if Self::get_doctor_status(env.clone(), wallet.clone()) != DoctorStatus::Active {
+            panic!("Doctor is not active");
+        }
+        Self::set_status(&env, &wallet, DoctorStatus::Suspended, reason);
+    }
+
+    /// Reinstate a suspended or retired doctor (admin or licensing authority only)
+    ///
+    /// # Arguments
+    /// * `authority` - The admin or licensing authority
+    /// * `wallet` - The wallet address of the doctor
+    /// * `reason` - Why the doctor is reinstated
+    pub fn reinstate_doctor(env: Env, authority: Address, wallet: Address, reason: String) {
+        Self::require_authority(&env, &authority);
+
+        match Self::get_doctor_status(env.clone(), wallet.clone()) {
+            DoctorStatus::Suspended | DoctorStatus::Retired => {}
+            _ => panic!("Doctor cannot be reinstated"),
+        }
+        Self::set_status(&env, &wallet, DoctorStatus::Active, reason);
+    }
+
+    /// Record a doctor's death (admin or licensing authority only). This is final.
+    ///
+    /// # Arguments
+    /// * `authority` - The admin or licensing authority
+    /// * `wallet` - The wallet address of the doctor
+    /// * `reason` - Supporting details, e.g. a death record reference
+    pub fn mark_doctor_deceased(env: Env, authority: Address, wallet: Address, reason: String) {
+        Self::require_authority(&env, &authority);
+
+        if Self::get_doctor_status(env.clone(), wallet.clone()) == DoctorStatus::Deceased {
+            panic!("Doctor already marked deceased");
+        }
+        Self::set_status(&env, &wallet, DoctorStatus::Deceased, reason);
+    }
+
+    /// Retrieve a doctor's current status
+    pub fn get_doctor_status(env: Env, wallet: Address) -> DoctorStatus {
+        Self::get_doctor_profile(env, wallet).status
+    }
+
+    /// Check whether a doctor is registered and active. Dependent contracts such
+    /// as referrals and prescriptions should refuse work for inactive doctors.
+    pub fn is_active_doctor(env: Env, wallet: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, DoctorProfileData>(&DataKey::Doctor(wallet))
+            .is_some_and(|profile| profile.status == DoctorStatus::Active)
+    }
+
+    /// Check whether a doctor is active and holds a valid medical license in at
+    /// least one jurisdiction. This is the verification other registries resolve.
+    pub fn is_verified_doctor(env: Env, wallet: Address) -> bool {
+        if !Self::is_active_doctor(env.clone(), wallet.clone()) {
+            return false;
+        }
+        let credentials = Self::get_doctor_credentials(env.clone(), wallet);
+        credentials
+            .licenses
+            .iter()
+            .any(|l| Self::is_current(&env, &l.issuer, l.expires_at, l.revoked))
+    }
+
+    // =====================================================
+    //                    LEGACY IMPORT
+    // =====================================================
+
+    /// Copy doctor records from the patient registry's `MedicalRegistry` (admin only).
+    /// Wallets that already have a profile, are unknown to the registry or have a
+    /// specialization that cannot be normalized are skipped. The legacy `verified`
+    /// flag is not carried over: imported doctors need an attested license to
+    /// count as verified. May be called in batches until `finish_legacy_import`.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `registry` - The address of the `MedicalRegistry` contract
+    /// * `wallets` - The doctor wallets to import
+    ///
+    /// # Returns
+    /// The number of doctors imported
+    pub fn import_legacy_doctors(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        wallets: Vec<Address>,
+    ) -> u32 {
+        Self::require_admin(&env, &admin);
+
+        if env.storage().instance().has(&DataKey::LegacyImportClosed) {
+            panic!("Legacy import already finished");
+        }
+
+        let legacy = MedicalRegistryClient::new(&env, &registry);
+        let mut imported = 0;
+        for wallet in wallets.iter() {
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Doctor(wallet.clone()))
+            {
+                continue;
+            }
+            let doctor = match legacy.try_get_doctor(&wallet) {
+                Ok(Ok(doctor)) => doctor,
+                _ => continue,
+            };
+            let Some(specialty_code) = Self::specialty_code(&env, &doctor.specialization) else {
+                continue;
+            };
+
+            Self::store_new_profile(
+                &env,
+                &wallet,
+                doctor.name,
+                doctor.specialization,
+                specialty_code,
+            );
+            imported += 1;
+
+            env.events()
+                .publish((symbol_short!("doc_imp"), wallet), registry.clone());
+        }
+        imported
+    }
+
+    /// Close the legacy import for good (admin only)
+    pub fn finish_legacy_import(env: Env, admin: Address) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::LegacyImportClosed, &true);
+
+        env.events()
+            .publish((symbol_short!("imp_done"), admin), symbol_short!("success"));
+    }
+
+    // =====================================================
+    //                       REVIEWS
+    // =====================================================
+
+    /// Trust a contract that records appointments or visits to vouch for
+    /// reviewers (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `source` - The address of the appointment or visit contract
+    pub fn add_visit_source(env: Env, admin: Address, source: Address) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VisitSource(source.clone()), &true);
+
+        env.events().publish(
+            (symbol_short!("add_vsrc"), source),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Stop accepting new reviews backed by a visit contract (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `source` - The address of the appointment or visit contract
+    pub fn remove_visit_source(env: Env, admin: Address, source: Address) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::VisitSource(source.clone()));
+
+        env.events()
+            .publish((symbol_short!("rm_vsrc"), source), symbol_short!("success"));
+    }
+
+    /// Submit a review for a completed visit. The visit source is asked to
+    /// confirm the visit, and each visit can be reviewed once.
+    ///
+    /// # Arguments
+    /// * `patient` - The wallet address of the reviewing patient
+    /// * `doctor` - The wallet address of the doctor
+    /// * `visit_source` - The contract that recorded the visit
+    /// * `visit_id` - The visit id in that contract
+    /// * `rating` - Rating from 1 to 5
+    /// * `content_hash` - Hash of the review text stored off-chain
+    ///
+    /// # Returns
+    /// The id of the new review
+    pub fn submit_review(
+        env: Env,
+        patient: Address,
+        doctor: Address,
+        visit_source: Address,
+        visit_id: u64,
+        rating: u32,
+        content_hash: BytesN<32>,
+    ) -> u64 {
+        patient.require_auth();
+        Self::assert_doctor_exists(&env, &doctor);
+
+        if !(1..=5).contains(&rating) {
+            panic!("Invalid rating");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::VisitSource(visit_source.clone()))
+        {
+            panic!("Unknown visit source");
+        }
+        let visit_key = DataKey::VisitReview(visit_source.clone(), visit_id);
+        if env.storage().persistent().has(&visit_key) {
+            panic!("Visit already reviewed");
+        }
+        if !VisitSourceClient::new(&env, &visit_source)
+            .is_completed_visit(&visit_id, &patient, &doctor)
+        {
+            panic!("No completed visit with this doctor");
+        }
+
+        let review_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReviewCount)
+            .unwrap_or(0);
+        let review = Review {
+            review_id,
+            doctor: doctor.clone(),
+            patient,
+            visit_source,
+            visit_id,
+            rating,
+            content_hash,
+            created_at: env.ledger().timestamp(),
+            response_hash: None,
+            responded_at: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Review(review_id), &review);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReviewCount, &(review_id + 1));
+        env.storage().persistent().set(&visit_key, &review_id);
+
+        let reviews_key = DataKey::DoctorReviews(doctor.clone());
+        let mut reviews: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&reviews_key)
+            .unwrap_or(Vec::new(&env));
+        reviews.push_back(review_id);
+        env.storage().persistent().set(&reviews_key, &reviews);
+
+        let mut summary = Self::get_rating_summary(env.clone(), doctor.clone());
+        summary.review_count += 1;
+        summary.rating_total += rating as u64;
+        summary.average_rating = (summary.rating_total * 100 / summary.review_count as u64) as u32;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Rating(doctor.clone()), &summary);
+
+        env.events()
+            .publish((symbol_short!("review"), doctor, review_id), rating);
+
+        review_id
+    }
+
+    /// Respond to a review of the doctor's own. Each review takes one response.
+    ///
+    /// # Arguments
+    /// * `doctor` - The wallet address of the reviewed doctor
+    /// * `review_id` - The id of the review
+    /// * `content_hash` - Hash of the response text stored off-chain
+    pub fn respond_to_review(env: Env, doctor: Address, review_id: u64, content_hash: BytesN<32>) {
+        doctor.require_auth();
+
+        let key = DataKey::Review(review_id);
+        let mut review: Review = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Review not found");
+
+        if review.doctor != doctor {
+            panic!("Only the reviewed doctor can respond");
+        }
+        if review.response_hash.is_some() {
+            panic!("Review already has a response");
+        }
+
+        review.response_hash = Some(content_hash);
+        review.responded_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&key, &review);
+
+        env.events().publish(
+            (symbol_short!("rev_resp"), doctor, review_id),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Retrieve a review by id
+    pub fn get_review(env: Env, review_id: u64) -> Review {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Review(review_id))
+            .expect("Review not found")
+    }
+
+    /// List a doctor's reviews, oldest first
+    ///
+    /// # Arguments
+    /// * `doctor` - The wallet address of the doctor
+    /// * `offset` - Number of reviews to skip
+    /// * `limit` - Maximum number of reviews to return (capped at 50)
+    pub fn get_doctor_reviews(env: Env, doctor: Address, offset: u32, limit: u32) -> Vec<Review> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DoctorReviews(doctor))
+            .unwrap_or(Vec::new(&env));
+
+        let end = offset
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(ids.len());
+        let mut reviews = Vec::new(&env);
+        for i in offset..end {
+            reviews.push_back(Self::get_review(env.clone(), ids.get_unchecked(i)));
+        }
+        reviews
+    }
+
+    /// Retrieve a doctor's aggregate rating
+    pub fn get_rating_summary(env: Env, doctor: Address) -> RatingSummary {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Rating(doctor))
+            .unwrap_or(RatingSummary {
+                review_count: 0,
+                rating_total: 0,
+                average_rating: 0,
+            })
+    }
 }
 
 mod test;
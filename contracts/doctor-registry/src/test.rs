@@ -1,11 +1,14 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Bytes, BytesN, Env, String, Symbol,
+};
 
 #[test]
 fn test_create_doctor_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, DoctorRegistry);
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
@@ -24,14 +27,18 @@ fn test_create_doctor_profile() {
 
     assert_eq!(profile.name, String::from_str(&env, "Dr. John Smith"));
     assert_eq!(profile.specialization, String::from_str(&env, "Cardiology"));
-    assert_eq!(profile.institution_wallet, institution_wallet);
     assert_eq!(profile.metadata, String::from_str(&env, ""));
+
+    // The claimed institution has not confirmed the affiliation yet
+    let affiliation = client.get_affiliation(&doctor_wallet, &institution_wallet);
+    assert_eq!(affiliation.status, AffiliationStatus::Pending);
+    assert!(client.get_affiliations(&doctor_wallet).is_empty());
 }
 
 #[test]
 fn test_update_doctor_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, DoctorRegistry);
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
@@ -69,7 +76,7 @@ fn test_update_doctor_profile() {
 #[should_panic(expected = "Doctor profile already exists")]
 fn test_duplicate_profile_creation() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, DoctorRegistry);
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
@@ -97,7 +104,7 @@ fn test_duplicate_profile_creation() {
 #[should_panic(expected = "Doctor profile not found")]
 fn test_get_nonexistent_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, DoctorRegistry);
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
@@ -109,7 +116,7 @@ fn test_get_nonexistent_profile() {
 #[should_panic(expected = "Doctor profile not found")]
 fn test_update_nonexistent_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, DoctorRegistry);
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
@@ -126,7 +133,7 @@ fn test_update_nonexistent_profile() {
 #[test]
 fn test_multiple_doctors() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, DoctorRegistry);
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor1_wallet = Address::generate(&env);
@@ -161,3 +168,605 @@ fn test_multiple_doctors() {
         String::from_str(&env, "Orthopedics")
     );
 }
+
+fn setup_credentials(env: &Env) -> (DoctorRegistryClient<'_>, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(DoctorRegistry, (admin.clone(),));
+    let client = DoctorRegistryClient::new(env, &contract_id);
+
+    let issuer = Address::generate(env);
+    let doctor_wallet = Address::generate(env);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    client.add_credential_issuer(&admin, &issuer);
+    client.create_doctor_profile(
+        &doctor_wallet,
+        &String::from_str(env, "Dr. Carol"),
+        &String::from_str(env, "Cardiology"),
+        &Address::generate(env),
+    );
+
+    (client, admin, issuer, doctor_wallet)
+}
+
+#[test]
+fn test_license_validity() {
+    let env = Env::default();
+    let (client, _admin, issuer, doctor_wallet) = setup_credentials(&env);
+    let california = String::from_str(&env, "US-CA");
+
+    assert!(!client.has_valid_license(&doctor_wallet, &california));
+
+    client.attest_license(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "A123456"),
+        &california,
+        &5_000,
+    );
+
+    assert!(client.has_valid_license(&doctor_wallet, &california));
+    assert!(!client.has_valid_license(&doctor_wallet, &String::from_str(&env, "US-NY")));
+    assert!(client.is_verified_doctor(&doctor_wallet));
+
+    // Renewal replaces the license for the same jurisdiction
+    client.attest_license(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "A123456"),
+        &california,
+        &10_000,
+    );
+    let credentials = client.get_doctor_credentials(&doctor_wallet);
+    assert_eq!(credentials.licenses.len(), 1);
+    assert_eq!(credentials.licenses.get(0).unwrap().expires_at, 10_000);
+
+    env.ledger().set_timestamp(10_000);
+    assert!(!client.has_valid_license(&doctor_wallet, &california));
+}
+
+#[test]
+fn test_revoked_and_deregistered_credentials_are_invalid() {
+    let env = Env::default();
+    let (client, admin, issuer, doctor_wallet) = setup_credentials(&env);
+    let california = String::from_str(&env, "US-CA");
+    let cardiology = String::from_str(&env, "Cardiovascular Disease");
+
+    client.attest_license(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "A123456"),
+        &california,
+        &5_000,
+    );
+    client.attest_board_certification(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "ABIM"),
+        &cardiology,
+        &String::from_str(&env, "C-998"),
+        &5_000,
+    );
+    client.attest_dea_registration(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "BC1234563"),
+        &california,
+        &vec![&env, 3, 4, 5],
+        &5_000,
+    );
+
+    assert!(client.has_valid_board_certification(&doctor_wallet, &cardiology));
+    assert!(client.has_valid_dea_registration(&doctor_wallet, &california, &4));
+    assert!(!client.has_valid_dea_registration(&doctor_wallet, &california, &2));
+
+    client.revoke_license(&issuer, &doctor_wallet, &california);
+    assert!(!client.has_valid_license(&doctor_wallet, &california));
+
+    client.remove_credential_issuer(&admin, &issuer);
+    assert!(!client.has_valid_board_certification(&doctor_wallet, &cardiology));
+    assert!(!client.has_valid_dea_registration(&doctor_wallet, &california, &4));
+}
+
+#[test]
+fn test_attestation_does_not_overwrite_other_entries() {
+    let env = Env::default();
+    let (client, admin, issuer, doctor_wallet) = setup_credentials(&env);
+    let other_issuer = Address::generate(&env);
+    client.add_credential_issuer(&admin, &other_issuer);
+    let california = String::from_str(&env, "US-CA");
+
+    client.attest_license(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "A123456"),
+        &california,
+        &5_000,
+    );
+    client.revoke_license(&issuer, &doctor_wallet, &california);
+
+    // A new license is added alongside the revoked one
+    client.attest_license(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "A123457"),
+        &california,
+        &5_000,
+    );
+    // Another issuer cannot overwrite the first issuer's license
+    client.attest_license(
+        &other_issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "X000001"),
+        &california,
+        &2_000,
+    );
+
+    let licenses = client.get_doctor_credentials(&doctor_wallet).licenses;
+    assert_eq!(licenses.len(), 3);
+    assert!(licenses.get(0).unwrap().revoked);
+    let current = licenses.get(1).unwrap();
+    assert_eq!(current.issuer, issuer);
+    assert_eq!(current.license_number, String::from_str(&env, "A123457"));
+    assert_eq!(current.expires_at, 5_000);
+    assert_eq!(licenses.get(2).unwrap().issuer, other_issuer);
+
+    client.revoke_license(&other_issuer, &doctor_wallet, &california);
+    env.ledger().set_timestamp(3_000);
+    assert!(client.has_valid_license(&doctor_wallet, &california));
+    assert!(
+        !client
+            .get_doctor_credentials(&doctor_wallet)
+            .licenses
+            .get(1)
+            .unwrap()
+            .revoked
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only the issuer can revoke this credential")]
+fn test_issuer_cannot_revoke_other_issuers_license() {
+    let env = Env::default();
+    let (client, admin, issuer, doctor_wallet) = setup_credentials(&env);
+    let other_issuer = Address::generate(&env);
+    client.add_credential_issuer(&admin, &other_issuer);
+    let california = String::from_str(&env, "US-CA");
+
+    client.attest_license(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "A123456"),
+        &california,
+        &5_000,
+    );
+    client.revoke_license(&other_issuer, &doctor_wallet, &california);
+}
+
+#[test]
+#[should_panic(expected = "Issuer is not a credential issuer")]
+fn test_unregistered_issuer_cannot_attest() {
+    let env = Env::default();
+    let (client, _admin, _issuer, doctor_wallet) = setup_credentials(&env);
+
+    client.attest_license(
+        &Address::generate(&env),
+        &doctor_wallet,
+        &String::from_str(&env, "A123456"),
+        &String::from_str(&env, "US-CA"),
+        &5_000,
+    );
+}
+
+#[test]
+fn test_affiliation_lifecycle() {
+    let env = Env::default();
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+
+    let doctor_wallet = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    let clinic = Address::generate(&env);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    client.create_doctor_profile(
+        &doctor_wallet,
+        &String::from_str(&env, "Dr. Dana"),
+        &String::from_str(&env, "Surgery"),
+        &hospital,
+    );
+    client.request_affiliation(&doctor_wallet, &clinic, &1_000, &Some(5_000));
+
+    client.confirm_affiliation(
+        &hospital,
+        &doctor_wallet,
+        &vec![&env, Privilege::Admitting, Privilege::Surgical],
+    );
+    client.confirm_affiliation(&clinic, &doctor_wallet, &vec![&env, Privilege::Consulting]);
+
+    let affiliations = client.get_affiliations(&doctor_wallet);
+    assert_eq!(affiliations.len(), 2);
+    assert_eq!(
+        affiliations.get(0).unwrap().privileges,
+        vec![&env, Privilege::Admitting, Privilege::Surgical]
+    );
+    assert!(client.is_affiliated(&doctor_wallet, &clinic));
+
+    // The clinic affiliation lapses at its end date
+    env.ledger().set_timestamp(5_000);
+    assert!(!client.is_affiliated(&doctor_wallet, &clinic));
+
+    client.terminate_affiliation(&hospital, &doctor_wallet);
+    assert!(client.get_affiliations(&doctor_wallet).is_empty());
+    assert_eq!(
+        client.get_affiliation(&doctor_wallet, &hospital).status,
+        AffiliationStatus::Terminated
+    );
+
+    // A terminated affiliation can be requested again
+    client.request_affiliation(&doctor_wallet, &hospital, &6_000, &None);
+    assert_eq!(
+        client.get_affiliation(&doctor_wallet, &hospital).status,
+        AffiliationStatus::Pending
+    );
+}
+
+#[test]
+#[should_panic(expected = "Affiliation not found")]
+fn test_unrequested_affiliation_cannot_be_confirmed() {
+    let env = Env::default();
+    let (client, _admin, _issuer, doctor_wallet) = setup_credentials(&env);
+
+    client.confirm_affiliation(
+        &Address::generate(&env),
+        &doctor_wallet,
+        &vec![&env, Privilege::Consulting],
+    );
+}
+
+#[test]
+fn test_directory_search() {
+    let env = Env::default();
+    let contract_id = env.register(DoctorRegistry, (Address::generate(&env),));
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let hospital = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    for (wallet, specialization) in [
+        (&alice, "Pediatric Neurology"),
+        (&bob, "pediatric  neurology"),
+        (&carol, "Cardiology"),
+    ] {
+        client.create_doctor_profile(
+            wallet,
+            &String::from_str(&env, "Dr. Test"),
+            &String::from_str(&env, specialization),
+            &hospital,
+        );
+    }
+
+    let neurology = String::from_str(&env, "Pediatric-Neurology");
+    assert_eq!(
+        client.get_doctor_profile(&bob).specialty_code,
+        String::from_str(&env, "PEDIATRIC_NEUROLOGY")
+    );
+    assert_eq!(
+        client.get_doctors_by_specialty(&neurology, &0, &10),
+        vec![&env, alice.clone(), bob.clone()]
+    );
+    assert_eq!(
+        client.get_doctors_by_specialty(&neurology, &1, &10),
+        vec![&env, bob.clone()]
+    );
+
+    let spanish = String::from_str(&env, "es");
+    client.set_directory_listing(&alice, &false, &vec![&env, spanish.clone()]);
+    client.set_directory_listing(&bob, &true, &vec![&env, spanish.clone()]);
+    assert_eq!(
+        client
+            .search_doctors(&neurology, &true, &Some(spanish.clone()), &0, &10)
+            .doctors,
+        vec![&env, bob.clone()]
+    );
+    assert_eq!(
+        client
+            .search_doctors(&neurology, &false, &Some(spanish.clone()), &0, &10)
+            .doctors,
+        vec![&env, alice.clone(), bob.clone()]
+    );
+
+    // Pages examine at most `limit` entries and resume from the returned cursor
+    let first = client.search_doctors(&neurology, &false, &Some(spanish.clone()), &0, &1);
+    assert_eq!(first.doctors, vec![&env, alice.clone()]);
+    assert_eq!(first.next_cursor, Some(1));
+    let second = client.search_doctors(&neurology, &false, &Some(spanish.clone()), &1, &1);
+    assert_eq!(second.doctors, vec![&env, bob.clone()]);
+    assert_eq!(second.next_cursor, None);
+    let short = client.search_doctors(&neurology, &true, &Some(spanish), &0, &1);
+    assert_eq!(short.doctors, vec![&env]);
+    assert_eq!(short.next_cursor, Some(1));
+
+    // Changing specialization moves the doctor between indexes
+    client.update_doctor_profile(
+        &carol,
+        &String::from_str(&env, "Pediatric Neurology"),
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(
+        client
+            .get_doctors_by_specialty(&String::from_str(&env, "Cardiology"), &0, &10)
+            .len(),
+        0
+    );
+    assert_eq!(
+        client.get_doctors_by_specialty(&neurology, &0, &10).len(),
+        3
+    );
+
+    // Only confirmed affiliations are listed under the institution
+    assert!(client
+        .get_doctors_by_institution(&hospital, &0, &10)
+        .doctors
+        .is_empty());
+    client.confirm_affiliation(&hospital, &carol, &vec![&env, Privilege::Consulting]);
+    assert_eq!(
+        client
+            .get_doctors_by_institution(&hospital, &0, &10)
+            .doctors,
+        vec![&env, carol.clone()]
+    );
+    client.terminate_affiliation(&hospital, &carol);
+    assert!(client
+        .get_doctors_by_institution(&hospital, &0, &10)
+        .doctors
+        .is_empty());
+}
+
+#[test]
+fn test_doctor_status_transitions() {
+    let env = Env::default();
+    let (client, admin, issuer, doctor_wallet) = setup_credentials(&env);
+    let cardiology = String::from_str(&env, "Cardiology");
+
+    assert!(client.is_active_doctor(&doctor_wallet));
+    assert!(!client.is_active_doctor(&Address::generate(&env)));
+
+    client.suspend_doctor(
+        &issuer,
+        &doctor_wallet,
+        &String::from_str(&env, "Board investigation"),
+    );
+    let profile = client.get_doctor_profile(&doctor_wallet);
+    assert_eq!(profile.status, DoctorStatus::Suspended);
+    assert_eq!(
+        profile.status_reason,
+        String::from_str(&env, "Board investigation")
+    );
+    assert!(!client.is_active_doctor(&doctor_wallet));
+    assert!(client
+        .get_doctors_by_specialty(&cardiology, &0, &10)
+        .is_empty());
+
+    client.reinstate_doctor(
+        &admin,
+        &doctor_wallet,
+        &String::from_str(&env, "Investigation closed"),
+    );
+    assert!(client.is_active_doctor(&doctor_wallet));
+    assert_eq!(
+        client.get_doctors_by_specialty(&cardiology, &0, &10),
+        vec![&env, doctor_wallet.clone()]
+    );
+
+    client.retire_doctor(&doctor_wallet);
+    assert_eq!(
+        client.get_doctor_status(&doctor_wallet),
+        DoctorStatus::Retired
+    );
+
+    client.mark_doctor_deceased(&admin, &doctor_wallet, &String::from_str(&env, ""));
+    assert!(client
+        .try_reinstate_doctor(&admin, &doctor_wallet, &String::from_str(&env, ""))
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Only admin or a licensing authority can perform this action")]
+fn test_only_authority_can_suspend() {
+    let env = Env::default();
+    let (client, _admin, _issuer, doctor_wallet) = setup_credentials(&env);
+
+    client.suspend_doctor(
+        &Address::generate(&env),
+        &doctor_wallet,
+        &String::from_str(&env, "No reason"),
+    );
+}
+
+/// Stand-in for the patient registry's `MedicalRegistry` doctor records
+#[contract]
+struct LegacyRegistry;
+
+#[contractimpl]
+impl LegacyRegistry {
+    pub fn seed(env: Env, wallet: Address, doctor: LegacyDoctorData) {
+        env.storage().persistent().set(&wallet, &doctor);
+    }
+
+    pub fn get_doctor(env: Env, wallet: Address) -> LegacyDoctorData {
+        env.storage()
+            .persistent()
+            .get(&wallet)
+            .expect("Doctor not found")
+    }
+}
+
+#[test]
+fn test_import_legacy_doctors() {
+    let env = Env::default();
+    let (client, admin, _issuer, existing) = setup_credentials(&env);
+
+    let legacy_id = env.register(LegacyRegistry, ());
+    let legacy = LegacyRegistryClient::new(&env, &legacy_id);
+    let migrated = Address::generate(&env);
+    let unknown = Address::generate(&env);
+    legacy.seed(
+        &migrated,
+        &LegacyDoctorData {
+            name: String::from_str(&env, "Dr. Legacy"),
+            specialization: String::from_str(&env, "Family Medicine"),
+            certificate_hash: Bytes::from_array(&env, &[1, 2, 3]),
+            verified: true,
+        },
+    );
+
+    let imported = client.import_legacy_doctors(
+        &admin,
+        &legacy_id,
+        &vec![&env, migrated.clone(), unknown, existing],
+    );
+    assert_eq!(imported, 1);
+
+    let profile = client.get_doctor_profile(&migrated);
+    assert_eq!(profile.name, String::from_str(&env, "Dr. Legacy"));
+    assert_eq!(
+        profile.specialty_code,
+        String::from_str(&env, "FAMILY_MEDICINE")
+    );
+    assert!(client.is_active_doctor(&migrated));
+    // Verification needs an attested license, not the legacy flag
+    assert!(!client.is_verified_doctor(&migrated));
+
+    client.finish_legacy_import(&admin);
+    assert!(client
+        .try_import_legacy_doctors(&admin, &legacy_id, &vec![&env, migrated])
+        .is_err());
+}
+
+/// Schedules and completes a telemedicine visit, returning its id
+fn complete_visit(
+    env: &Env,
+    visits: &telemedicine::contract::TelemedicineContractClient,
+    patient: &Address,
+    doctor: &Address,
+) -> u64 {
+    let visit_id = visits.schedule_virtual_visit(
+        patient,
+        doctor,
+        &2_000,
+        &Symbol::new(env, "Consult"),
+        &30,
+        &Symbol::new(env, "Video"),
+        &true,
+    );
+    visits.start_virtual_session(&visit_id, doctor, &2_000, &String::from_str(env, "CA"));
+    visits.end_virtual_session(&visit_id, doctor, &3_800, &30);
+    visit_id
+}
+
+#[test]
+fn test_verified_reviews_and_responses() {
+    let env = Env::default();
+    let (client, admin, _issuer, doctor_wallet) = setup_credentials(&env);
+    let visits_id = env.register(telemedicine::contract::TelemedicineContract, ());
+    let visits = telemedicine::contract::TelemedicineContractClient::new(&env, &visits_id);
+    client.add_visit_source(&admin, &visits_id);
+
+    let patient = Address::generate(&env);
+    let first_visit = complete_visit(&env, &visits, &patient, &doctor_wallet);
+    let second_visit = complete_visit(&env, &visits, &patient, &doctor_wallet);
+
+    let review_id = client.submit_review(
+        &patient,
+        &doctor_wallet,
+        &visits_id,
+        &first_visit,
+        &4,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    client.submit_review(
+        &patient,
+        &doctor_wallet,
+        &visits_id,
+        &second_visit,
+        &5,
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+
+    let summary = client.get_rating_summary(&doctor_wallet);
+    assert_eq!(summary.review_count, 2);
+    assert_eq!(summary.average_rating, 450);
+
+    // Each visit can only be reviewed once
+    assert!(client
+        .try_submit_review(
+            &patient,
+            &doctor_wallet,
+            &visits_id,
+            &first_visit,
+            &1,
+            &BytesN::from_array(&env, &[3; 32]),
+        )
+        .is_err());
+
+    client.respond_to_review(
+        &doctor_wallet,
+        &review_id,
+        &BytesN::from_array(&env, &[9; 32]),
+    );
+    let review = client.get_review(&review_id);
+    assert_eq!(
+        review.response_hash,
+        Some(BytesN::from_array(&env, &[9; 32]))
+    );
+    assert!(client
+        .try_respond_to_review(
+            &doctor_wallet,
+            &review_id,
+            &BytesN::from_array(&env, &[8; 32])
+        )
+        .is_err());
+
+    let reviews = client.get_doctor_reviews(&doctor_wallet, &1, &10);
+    assert_eq!(reviews.len(), 1);
+    assert_eq!(reviews.get(0).unwrap().rating, 5);
+}
+
+#[test]
+#[should_panic(expected = "No completed visit with this doctor")]
+fn test_review_requires_completed_visit() {
+    let env = Env::default();
+    let (client, admin, _issuer, doctor_wallet) = setup_credentials(&env);
+    let visits_id = env.register(telemedicine::contract::TelemedicineContract, ());
+    let visits = telemedicine::contract::TelemedicineContractClient::new(&env, &visits_id);
+    client.add_visit_source(&admin, &visits_id);
+
+    let patient = Address::generate(&env);
+    let visit_id = visits.schedule_virtual_visit(
+        &patient,
+        &doctor_wallet,
+        &2_000,
+        &Symbol::new(&env, "Consult"),
+        &30,
+        &Symbol::new(&env, "Video"),
+        &true,
+    );
+
+    client.submit_review(
+        &patient,
+        &doctor_wallet,
+        &visits_id,
+        &visit_id,
+        &5,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+}
@@ -1,5 +1,8 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, vec, xdr::ToXdr, Address, BytesN,
+    Env, Map, String, Vec,
+};
 
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -9,25 +12,250 @@ pub enum RecordType {
     Receipt = 2,
     BankStatement = 3,
     Other = 4,
+    Reimbursement = 5,
+}
+
+/// A monetary amount in minor units of an ISO 4217 currency (e.g. cents of "USD")
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Money {
+    pub amount: i128,
+    pub currency: String,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntryKind {
+    Original = 0,
+    Amendment = 1, // Replaces the record at `amends`
+    Void = 2,      // Voids the record at `amends`
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordStatus {
+    Active = 0,
+    Amended = 1,
+    Voided = 2,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FinancialRecord {
     pub owner: Address,
+    pub index: u32,
     pub record_type: RecordType,
     pub ipfs_hash: String,
     pub timestamp: u64,
     pub description: String,
+    pub amount: Money,           // Negative on refund receipts
+    pub tax_year: u32,           // Amendments keep the tax year of the record they replace
+    pub invoice_id: Option<u64>, // Invoice this record belongs to, if any
+    pub entry_kind: EntryKind,
+    pub amends: Option<u32>,   // Record corrected or voided by this entry
+    pub prev_hash: BytesN<32>, // Hash of the owner's previous record, zero for the first
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvoiceStatus {
+    Issued = 0,
+    PartiallyPaid = 1,
+    Paid = 2,
+    Void = 3,
+    Disputed = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceLineItem {
+    pub service_code: String,
+    pub quantity: u32,
+    pub unit_price: i128,
+    pub tax: i128, // Tax for the whole line
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invoice {
+    pub invoice_id: u64,
+    pub issuer: Address,
+    pub payer: Address,
+    pub record_index: u32, // Index of the issuer's Invoice FinancialRecord
+    pub line_items: Vec<InvoiceLineItem>,
+    pub subtotal: i128,
+    pub tax_total: i128,
+    pub total: i128,
+    pub currency: String,
+    pub payment_token: Option<Address>, // Token accepted for on-chain payment
+    pub amount_paid: i128,              // Net of refunds
+    pub due_date: u64,
+    pub status: InvoiceStatus,
+    pub issued_at: u64,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlanStatus {
+    Active = 0,
+    Delinquent = 1,
+    Completed = 2,
+    Cancelled = 3,
+}
+
+/// Installment schedule agreed between an invoice's issuer and payer.
+/// Installment `i` (from 0) falls due at `start_date + i * frequency`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentPlan {
+    pub plan_id: u64,
+    pub invoice_id: u64,
+    pub issuer: Address,
+    pub payer: Address,
+    pub principal: i128, // Invoice balance when the plan was agreed
+    pub installment_amount: i128,
+    pub frequency: u64, // Seconds between due dates
+    pub start_date: u64,
+    pub installment_count: u32,
+    pub amount_paid: i128,
+    pub installments_paid: u32,
+    pub missed_installments: u32, // As of the last payment or `mark_overdue` sweep
+    pub next_due_date: u64,       // 0 once the plan is no longer active
+    pub status: PlanStatus,
+    pub created_at: u64,
+}
+
+/// Patient-owned escrow holding a token balance that can only be released
+/// to registered providers against invoices for eligible services.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HsaVault {
+    pub owner: Address,
+    pub token: Address,
+    pub balance: i128,
+    pub total_deposited: i128,
+    pub total_released: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payment {
+    pub payment_id: u64,
+    pub invoice_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub is_refund: bool,
+    pub via_vault: bool,
+    pub timestamp: u64,
+}
+
+/// Read access granted by a record owner, limited to some record types,
+/// a range of record dates and an expiry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessGrant {
+    pub grantee: Address,
+    pub record_types: Vec<RecordType>, // Empty means all types
+    pub start: u64,                    // Earliest record timestamp visible
+    pub end: u64,                      // Latest record timestamp visible
+    pub expires_at: u64,
+    pub granted_at: u64,
+}
+
+/// Running totals for one owner, tax (calendar) year and currency.
+/// Counts and totals by type cover current records only, so amending or
+/// voiding a record moves or removes its contribution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxYearSummary {
+    pub year: u32,
+    pub currency: String,
+    pub record_counts: Map<RecordType, u32>,
+    pub record_totals: Map<RecordType, i128>,
+    pub medical_expenses_paid: i128, // Receipts and invoice payments, net of refunds
+    pub reimbursements_received: i128, // Reimbursement records
+    pub payments_received: i128,     // Invoice payments received as issuer, net of refunds
+    pub outstanding_invoices: i128,  // Unpaid balance of invoices issued this year, either side
+}
+
+/// A tax year's totals converted into a reporting currency, built a page of
+/// records at a time. Add up the pages until `next_cursor` is `None`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReportingSummary {
+    pub year: u32,
+    pub currency: String,
+    pub record_totals: Map<RecordType, i128>,
+    pub medical_expenses_paid: i128,
+    pub reimbursements_received: i128,
+    pub payments_received: i128,
+    pub next_cursor: Option<u32>,
+}
+
+/// Exchange rate in effect from `timestamp`, in quote minor units per base
+/// minor unit scaled by `RATE_SCALE`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateSnapshot {
+    pub rate: i128,
+    pub timestamp: u64,
+}
+
+/// A page of records and the cursor to pass for the next page, if any
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordPage {
+    pub records: Vec<FinancialRecord>,
+    pub next_cursor: Option<u32>,
+}
+
+/// One page of a hash chain check. `hash` is the running hash to pass as
+/// `expected_hash` together with `next_cursor` to check the next page.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChainCheck {
+    pub valid: bool,
+    pub hash: BytesN<32>,
+    pub next_cursor: Option<u32>,
 }
 
 #[contracttype]
 pub enum DataKey {
-    Record(Address, u32),     // (Owner, Index) -> FinancialRecord
-    RecordCount(Address),     // Owner -> Number of records
-    Access(Address, Address), // (Owner, Authorized) -> bool
+    Record(Address, u32),           // (Owner, Index) -> FinancialRecord
+    RecordCount(Address),           // Owner -> Number of records
+    Access(Address, Address),       // (Owner, Authorized) -> AccessGrant
+    Grantees(Address),              // Owner -> Vec<Address> of addresses holding a grant
+    InvoiceCount,                   // Last issued invoice id
+    Invoice(u64),                   // Invoice id -> Invoice
+    PayerInvoices(Address),         // Payer -> Vec<u64> of invoice ids
+    PaymentCount,                   // Last issued payment id
+    Payment(u64),                   // Payment id -> Payment
+    InvoicePayments(u64),           // Invoice id -> Vec<u64> of payment ids
+    TypeIndex(Address, RecordType), // (Owner, Type) -> Vec<u32> of record indexes
+    MonthIndex(Address, u32),       // (Owner, YYYYMM) -> Vec<u32> of record indexes
+    ChainHead(Address),             // Owner -> hash of the latest record
+    SupersededBy(Address, u32),     // (Owner, Index) -> index of the amending or void entry
+    TaxYear(Address, u32, String),  // (Owner, Year, Currency) -> TaxYearSummary
+    YearIndex(Address, u32),        // (Owner, Tax year) -> Vec<u32> of record indexes
+    PlanCount,                      // Last issued payment plan id
+    PaymentPlan(u64),               // Plan id -> PaymentPlan
+    InvoicePlan(u64),               // Invoice id -> plan id of its current payment plan
+    Admin,
+    EligibleService(String),         // Service code -> bool
+    Provider(Address),               // Registered provider -> bool
+    Vault(Address),                  // Patient -> HsaVault
+    ProviderCap(Address, Address),   // (Patient, Provider) -> spending cap
+    ProviderSpent(Address, Address), // (Patient, Provider) -> total released
+    RateOracle(Address),             // Oracle -> bool
+    Rates(String, String),           // (Base, Quote) -> Vec<RateSnapshot> ordered by timestamp
 }
 
+const MAX_PAGE_SIZE: u32 = 50;
+const MAX_MONTHS_PER_PAGE: u32 = 12;
+const RATE_SCALE: i128 = 10_000_000;
+
 #[contract]
 pub struct FinancialRecordContract;
 
@@ -40,147 +268,1825 @@ impl FinancialRecordContract {
         record_type: RecordType,
         ipfs_hash: String,
         description: String,
+        amount: Money,
     ) {
         owner.require_auth();
+        Self::check_money(&amount);
+        Self::store_record(
+            &e,
+            Self::new_record(&e, &owner, record_type, ipfs_hash, description, amount),
+        );
+    }
+
+    /// Corrects a record by appending an amendment that replaces it.
+    /// The original is kept and reported as `Amended`.
+    pub fn amend_financial_record(
+        e: Env,
+        owner: Address,
+        index: u32,
+        ipfs_hash: String,
+        description: String,
+        amount: Money,
+    ) -> u32 {
+        owner.require_auth();
+        Self::check_money(&amount);
+
+        let original = Self::load_correctable(&e, &owner, index);
+        let mut record = Self::new_record(
+            &e,
+            &owner,
+            original.record_type,
+            ipfs_hash,
+            description,
+            amount,
+        );
+        record.entry_kind = EntryKind::Amendment;
+        record.amends = Some(index);
+        record.tax_year = original.tax_year;
+        Self::supersede(&e, original, record)
+    }
+
+    /// Voids a record by appending a void entry carrying the reason.
+    /// The original is kept and reported as `Voided`.
+    pub fn void_financial_record(e: Env, owner: Address, index: u32, reason: String) -> u32 {
+        owner.require_auth();
+
+        let original = Self::load_correctable(&e, &owner, index);
+        let mut record = Self::new_record(
+            &e,
+            &owner,
+            original.record_type,
+            String::from_str(&e, ""),
+            reason,
+            Money {
+                amount: 0,
+                currency: original.amount.currency.clone(),
+            },
+        );
+        record.entry_kind = EntryKind::Void;
+        record.amends = Some(index);
+        record.tax_year = original.tax_year;
+        Self::supersede(&e, original, record)
+    }
+
+    /// Retrieves a single record.
+    pub fn get_record(e: Env, caller: Address, owner: Address, index: u32) -> FinancialRecord {
+        let grant = Self::check_access(&e, &caller, &owner);
+        let record = Self::load_record(&e, &owner, index);
+        if !Self::grant_covers(&grant, record.record_type, record.timestamp) {
+            panic!("Access denied");
+        }
+        record
+    }
+
+    /// Whether a record is still current, or has been amended or voided.
+    pub fn get_record_status(e: Env, caller: Address, owner: Address, index: u32) -> RecordStatus {
+        Self::get_record(e.clone(), caller, owner.clone(), index);
+
+        let superseded_by: Option<u32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::SupersededBy(owner.clone(), index));
+        match superseded_by {
+            None => RecordStatus::Active,
+            Some(entry) => match Self::load_record(&e, &owner, entry).entry_kind {
+                EntryKind::Void => RecordStatus::Voided,
+                _ => RecordStatus::Amended,
+            },
+        }
+    }
+
+    /// Retrieves the owner's running totals for a tax year in one currency.
+    /// Grants limited to specific record types cannot read summaries.
+    pub fn get_tax_year_summary(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        year: u32,
+        currency: String,
+    ) -> TaxYearSummary {
+        Self::check_summary_access(&e, &caller, &owner, year);
+        Self::load_summary(&e, &owner, year, currency)
+    }
+
+    /// Converts a page of the owner's current records for a tax year into a
+    /// reporting currency, using the exchange rate in effect at each record's
+    /// timestamp. Outstanding invoice balances are only reported per currency
+    /// by `get_tax_year_summary`. Paging works as in `get_financial_records`.
+    pub fn get_reporting_summary(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        year: u32,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> ReportingSummary {
+        Self::check_summary_access(&e, &caller, &owner, year);
+
+        let mut report = ReportingSummary {
+            year,
+            currency: currency.clone(),
+            record_totals: Map::new(&e),
+            medical_expenses_paid: 0,
+            reimbursements_received: 0,
+            payments_received: 0,
+            next_cursor: None,
+        };
+        let ids: Vec<u32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::YearIndex(owner.clone(), year))
+            .unwrap_or(vec![&e]);
+        let from = Self::lower_bound(&ids, cursor);
+        let end = from.saturating_add(limit.min(MAX_PAGE_SIZE)).min(ids.len());
+
+        for pos in from..end {
+            let index = ids.get_unchecked(pos);
+            if e.storage()
+                .persistent()
+                .has(&DataKey::SupersededBy(owner.clone(), index))
+            {
+                continue;
+            }
+            let record = Self::load_record(&e, &owner, index);
+            let amount = Self::convert(&e, &record.amount, &currency, record.timestamp);
+
+            let total = report.record_totals.get(record.record_type).unwrap_or(0);
+            report.record_totals.set(record.record_type, total + amount);
+            match (record.record_type, record.invoice_id) {
+                (RecordType::Receipt, None) => report.medical_expenses_paid += amount,
+                (RecordType::Reimbursement, None) => report.reimbursements_received += amount,
+                (RecordType::Receipt, Some(invoice_id)) => {
+                    if Self::load_invoice(&e, invoice_id).payer == owner {
+                        report.medical_expenses_paid += amount;
+                    } else {
+                        report.payments_received += amount;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if end < ids.len() {
+            report.next_cursor = Some(ids.get_unchecked(end));
+        }
+        report
+    }
+
+    /// Allows an oracle to publish exchange rates (admin only).
+    pub fn add_rate_oracle(e: Env, admin: Address, oracle: Address) {
+        Self::require_admin(&e, &admin);
+        e.storage()
+            .persistent()
+            .set(&DataKey::RateOracle(oracle), &true);
+    }
+
+    /// Revokes an oracle's permission to publish exchange rates (admin only).
+    pub fn remove_rate_oracle(e: Env, admin: Address, oracle: Address) {
+        Self::require_admin(&e, &admin);
+        e.storage()
+            .persistent()
+            .remove(&DataKey::RateOracle(oracle));
+    }
+
+    /// Records the exchange rate from `base` to `quote` in effect from `timestamp`,
+    /// in quote minor units per base minor unit scaled by 10^7.
+    /// Publishing again for the same timestamp replaces the earlier rate.
+    pub fn set_exchange_rate(
+        e: Env,
+        source: Address,
+        base: String,
+        quote: String,
+        rate: i128,
+        timestamp: u64,
+    ) {
+        source.require_auth();
+        let admin: Option<Address> = e.storage().instance().get(&DataKey::Admin);
+        if admin != Some(source.clone())
+            && !e.storage().persistent().has(&DataKey::RateOracle(source))
+        {
+            panic!("Only admin or a rate oracle can set rates");
+        }
 
+        Self::check_currency(&base);
+        Self::check_currency(&quote);
+        if base == quote {
+            panic!("Invalid currency pair");
+        }
+        if rate <= 0 {
+            panic!("Invalid rate");
+        }
+        if timestamp > e.ledger().timestamp() {
+            panic!("Rate timestamp in the future");
+        }
+
+        let key = DataKey::Rates(base, quote);
+        let mut rates: Vec<RateSnapshot> = e.storage().persistent().get(&key).unwrap_or(vec![&e]);
+        let pos = Self::rate_position(&rates, timestamp);
+        let snapshot = RateSnapshot { rate, timestamp };
+        if pos > 0 && rates.get_unchecked(pos - 1).timestamp == timestamp {
+            rates.set(pos - 1, snapshot);
+        } else {
+            rates.insert(pos, snapshot);
+        }
+        e.storage().persistent().set(&key, &rates);
+    }
+
+    /// Retrieves the exchange rate from `base` to `quote` in effect at `timestamp`,
+    /// scaled by 10^7. Falls back to inverting the `quote` to `base` rate.
+    pub fn get_exchange_rate(e: Env, base: String, quote: String, timestamp: u64) -> i128 {
+        if base == quote {
+            return RATE_SCALE;
+        }
+        if let Some(rate) = Self::rate_at(&e, &base, &quote, timestamp) {
+            return rate;
+        }
+        match Self::rate_at(&e, &quote, &base, timestamp) {
+            Some(rate) => RATE_SCALE * RATE_SCALE / rate,
+            None => panic!("No exchange rate available"),
+        }
+    }
+
+    /// Recomputes a page of the owner's record hash chain, starting at `cursor`
+    /// with `expected_hash` as the hash of the record before it (all zeros at
+    /// 0), and confirms that every record links to its predecessor. At most
+    /// `limit` (capped at 50) records are checked per call; the last page also
+    /// confirms that the final hash matches the stored head.
+    pub fn verify_chain(
+        e: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+        expected_hash: BytesN<32>,
+    ) -> ChainCheck {
         let count: u32 = e
             .storage()
             .persistent()
             .get(&DataKey::RecordCount(owner.clone()))
             .unwrap_or(0);
-        let timestamp = e.ledger().timestamp();
 
-        let record = FinancialRecord {
-            owner: owner.clone(),
-            record_type,
+        let mut check = ChainCheck {
+            valid: false,
+            hash: expected_hash,
+            next_cursor: None,
+        };
+        let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        for i in cursor..end {
+            let record: Option<FinancialRecord> = e
+                .storage()
+                .persistent()
+                .get(&DataKey::Record(owner.clone(), i));
+            let record = match record {
+                Some(record) => record,
+                None => return check,
+            };
+            if record.index != i || record.prev_hash != check.hash {
+                return check;
+            }
+            check.hash = Self::record_hash(&e, &record);
+        }
+
+        if end < count {
+            check.valid = true;
+            check.next_cursor = Some(end);
+            return check;
+        }
+        let head: BytesN<32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::ChainHead(owner))
+            .unwrap_or(BytesN::from_array(&e, &[0; 32]));
+        check.valid = head == check.hash;
+        check
+    }
+
+    /// Issues a structured invoice from `issuer` to `payer`.
+    /// An `Invoice` financial record is added for the issuer alongside it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_invoice(
+        e: Env,
+        issuer: Address,
+        payer: Address,
+        line_items: Vec<InvoiceLineItem>,
+        currency: String,
+        due_date: u64,
+        ipfs_hash: String,
+        description: String,
+    ) -> u64 {
+        issuer.require_auth();
+        Self::check_currency(&currency);
+
+        if line_items.is_empty() {
+            panic!("Invoice must have line items");
+        }
+        let mut subtotal: i128 = 0;
+        let mut tax_total: i128 = 0;
+        for item in line_items.iter() {
+            if item.quantity == 0 || item.unit_price < 0 || item.tax < 0 {
+                panic!("Invalid line item");
+            }
+            subtotal += item.unit_price * item.quantity as i128;
+            tax_total += item.tax;
+        }
+
+        let invoice_id: u64 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::InvoiceCount)
+            .unwrap_or(0)
+            + 1;
+        let total = subtotal + tax_total;
+        let mut record = Self::new_record(
+            &e,
+            &issuer,
+            RecordType::Invoice,
             ipfs_hash,
-            timestamp,
             description,
+            Money {
+                amount: total,
+                currency: currency.clone(),
+            },
+        );
+        record.invoice_id = Some(invoice_id);
+        let record_index = Self::store_record(&e, record);
+        let now = e.ledger().timestamp();
+        let invoice = Invoice {
+            invoice_id,
+            issuer,
+            payer: payer.clone(),
+            record_index,
+            line_items,
+            subtotal,
+            tax_total,
+            total,
+            currency,
+            payment_token: None,
+            amount_paid: 0,
+            due_date,
+            status: InvoiceStatus::Issued,
+            issued_at: now,
+            updated_at: now,
         };
 
         e.storage()
             .persistent()
-            .set(&DataKey::Record(owner.clone(), count), &record);
+            .set(&DataKey::Invoice(invoice_id), &invoice);
         e.storage()
             .persistent()
-            .set(&DataKey::RecordCount(owner.clone()), &(count + 1));
+            .set(&DataKey::InvoiceCount, &invoice_id);
+        Self::adjust_outstanding(&e, &invoice, total);
+
+        let payer_key = DataKey::PayerInvoices(payer);
+        let mut payer_invoices: Vec<u64> =
+            e.storage().persistent().get(&payer_key).unwrap_or(vec![&e]);
+        payer_invoices.push_back(invoice_id);
+        e.storage().persistent().set(&payer_key, &payer_invoices);
+
+        invoice_id
+    }
+
+    /// Retrieves an invoice.
+    /// Access is allowed for the payer, the issuer, and anyone the issuer has granted access.
+    pub fn get_invoice(e: Env, caller: Address, invoice_id: u64) -> Invoice {
+        let invoice = Self::load_invoice(&e, invoice_id);
+        if caller == invoice.payer {
+            caller.require_auth();
+        } else {
+            let grant = Self::check_access(&e, &caller, &invoice.issuer);
+            if !Self::grant_covers(&grant, RecordType::Invoice, invoice.issued_at) {
+                panic!("Access denied");
+            }
+        }
+        invoice
     }
 
-    /// Retrieves all financial records for an owner.
-    /// Access is allowed if the caller is the owner or has been granted access.
-    pub fn get_financial_records(e: Env, caller: Address, owner: Address) -> Vec<FinancialRecord> {
-        Self::check_access(&e, &caller, &owner);
+    /// Retrieves all invoices addressed to a payer.
+    pub fn get_payer_invoices(e: Env, caller: Address, payer: Address) -> Vec<Invoice> {
+        let grant = Self::check_access(&e, &caller, &payer);
 
-        let count: u32 = e
+        let ids: Vec<u64> = e
             .storage()
             .persistent()
-            .get(&DataKey::RecordCount(owner.clone()))
+            .get(&DataKey::PayerInvoices(payer))
+            .unwrap_or(vec![&e]);
+        let mut invoices = vec![&e];
+        for id in ids.iter() {
+            let invoice = Self::load_invoice(&e, id);
+            if Self::grant_covers(&grant, RecordType::Invoice, invoice.issued_at) {
+                invoices.push_back(invoice);
+            }
+        }
+        invoices
+    }
+
+    /// Updates an invoice's status.
+    /// The payer may dispute an open invoice; the issuer records off-chain
+    /// payments, resolves disputes and voids invoices with nothing paid on-chain.
+    /// Invoices with a payment token only become paid through `pay_invoice`,
+    /// and resolving a dispute restores the status implied by `amount_paid`.
+    pub fn update_invoice_status(e: Env, caller: Address, invoice_id: u64, status: InvoiceStatus) {
+        caller.require_auth();
+
+        let mut invoice = Self::load_invoice(&e, invoice_id);
+        let authorized = if status == InvoiceStatus::Disputed {
+            caller == invoice.payer
+        } else {
+            caller == invoice.issuer
+        };
+        if !authorized {
+            panic!("Not authorized to update invoice");
+        }
+
+        let current = invoice.status;
+        let open = current == InvoiceStatus::Issued || current == InvoiceStatus::PartiallyPaid;
+        let valid = match status {
+            InvoiceStatus::Disputed => open,
+            InvoiceStatus::Issued => current == InvoiceStatus::Disputed,
+            InvoiceStatus::PartiallyPaid | InvoiceStatus::Paid => {
+                if invoice.payment_token.is_some() {
+                    panic!("Invoice payments are recorded on-chain");
+                }
+                open || current == InvoiceStatus::Disputed
+            }
+            InvoiceStatus::Void => current != InvoiceStatus::Paid && current != InvoiceStatus::Void,
+        };
+        if !valid {
+            panic!("Invalid invoice status transition");
+        }
+        if status == InvoiceStatus::Void && invoice.amount_paid > 0 {
+            panic!("Refund payments before voiding");
+        }
+
+        let outstanding_before = Self::outstanding(&invoice);
+        invoice.status = if status == InvoiceStatus::Issued && invoice.amount_paid > 0 {
+            InvoiceStatus::PartiallyPaid
+        } else {
+            status
+        };
+        invoice.updated_at = e.ledger().timestamp();
+        e.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &invoice);
+        Self::adjust_outstanding(
+            &e,
+            &invoice,
+            Self::outstanding(&invoice) - outstanding_before,
+        );
+        Self::sync_plan(&e, &invoice, 0);
+    }
+
+    /// Agrees an installment plan for the remaining balance of an open invoice.
+    /// Both the issuer and the payer must authorize the plan. Payments made
+    /// with `pay_invoice` count towards the installments.
+    pub fn create_payment_plan(
+        e: Env,
+        invoice_id: u64,
+        installment_amount: i128,
+        frequency: u64,
+        start_date: u64,
+    ) -> u64 {
+        let invoice = Self::load_invoice(&e, invoice_id);
+        invoice.issuer.require_auth();
+        invoice.payer.require_auth();
+
+        if invoice.status != InvoiceStatus::Issued && invoice.status != InvoiceStatus::PartiallyPaid
+        {
+            panic!("Invoice is not payable");
+        }
+        if let Some(plan_id) = e
+            .storage()
+            .persistent()
+            .get::<DataKey, u64>(&DataKey::InvoicePlan(invoice_id))
+        {
+            let status = Self::load_plan(&e, plan_id).status;
+            if status == PlanStatus::Active || status == PlanStatus::Delinquent {
+                panic!("Invoice already has a payment plan");
+            }
+        }
+
+        let principal = invoice.total - invoice.amount_paid;
+        if installment_amount <= 0 || installment_amount > principal {
+            panic!("Invalid installment amount");
+        }
+        if frequency == 0 {
+            panic!("Invalid frequency");
+        }
+
+        let plan_id: u64 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::PlanCount)
+            .unwrap_or(0)
+            + 1;
+        let installment_count =
+            u32::try_from((principal + installment_amount - 1) / installment_amount)
+                .unwrap_or_else(|_| panic!("Invalid installment amount"));
+        let plan = PaymentPlan {
+            plan_id,
+            invoice_id,
+            issuer: invoice.issuer,
+            payer: invoice.payer,
+            principal,
+            installment_amount,
+            frequency,
+            start_date,
+            installment_count,
+            amount_paid: 0,
+            installments_paid: 0,
+            missed_installments: 0,
+            next_due_date: start_date,
+            status: PlanStatus::Active,
+            created_at: e.ledger().timestamp(),
+        };
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::PaymentPlan(plan_id), &plan);
+        e.storage().persistent().set(&DataKey::PlanCount, &plan_id);
+        e.storage()
+            .persistent()
+            .set(&DataKey::InvoicePlan(invoice_id), &plan_id);
+
+        plan_id
+    }
+
+    /// Retrieves a payment plan. Access follows the plan's invoice.
+    pub fn get_payment_plan(e: Env, caller: Address, plan_id: u64) -> PaymentPlan {
+        let plan = Self::load_plan(&e, plan_id);
+        Self::get_invoice(e, caller, plan.invoice_id);
+        plan
+    }
+
+    /// Permissionless sweep over plans `cursor..cursor + limit` (by id) that
+    /// flags plans with missed installments as delinquent. An event is emitted
+    /// for each plan whose missed installment count has grown.
+    /// Returns the cursor to continue the sweep from, if any plans remain.
+    pub fn mark_overdue(e: Env, cursor: u64, limit: u32) -> Option<u64> {
+        let count: u64 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::PlanCount)
             .unwrap_or(0);
-        let mut records = vec![&e];
+        let first = cursor.max(1);
+        let end = first
+            .saturating_add(limit.min(MAX_PAGE_SIZE) as u64)
+            .min(count + 1);
 
-        for i in 0..count {
-            if let Some(record) = e
-                .storage()
-                .persistent()
-                .get(&DataKey::Record(owner.clone(), i))
-            {
-                records.push_back(record);
+        for plan_id in first..end {
+            let mut plan = Self::load_plan(&e, plan_id);
+            if plan.status != PlanStatus::Active && plan.status != PlanStatus::Delinquent {
+                continue;
             }
+            let missed = Self::missed_installments(&e, &plan);
+            if missed > plan.missed_installments {
+                plan.missed_installments = missed;
+                plan.status = PlanStatus::Delinquent;
+                e.storage()
+                    .persistent()
+                    .set(&DataKey::PaymentPlan(plan_id), &plan);
+                e.events().publish(
+                    (symbol_short!("plan_odue"), plan.payer.clone(), plan_id),
+                    missed,
+                );
+            }
+        }
+
+        if end <= count {
+            Some(end)
+        } else {
+            None
         }
-        records
     }
 
-    /// Retrieves records within a specific date range.
-    pub fn get_records_by_date_range(
+    /// Retrieves a page of an owner's financial records, oldest first.
+    /// Access is allowed if the caller is the owner or has been granted access,
+    /// in which case only records within the grant's scope are returned.
+    ///
+    /// At most `limit` records are examined per call, starting at record index
+    /// `cursor`; a page may hold fewer records when a grant filters some out.
+    /// Pass the returned `next_cursor` to continue.
+    pub fn get_financial_records(
         e: Env,
         caller: Address,
         owner: Address,
-        start: u64,
-        end: u64,
-    ) -> Vec<FinancialRecord> {
-        Self::check_access(&e, &caller, &owner);
+        cursor: u32,
+        limit: u32,
+    ) -> RecordPage {
+        let grant = Self::check_access(&e, &caller, &owner);
 
         let count: u32 = e
             .storage()
             .persistent()
             .get(&DataKey::RecordCount(owner.clone()))
             .unwrap_or(0);
-        let mut records = vec![&e];
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let end = cursor.saturating_add(limit).min(count);
 
-        for i in 0..count {
-            if let Some(record) = e
-                .storage()
-                .persistent()
-                .get::<DataKey, FinancialRecord>(&DataKey::Record(owner.clone(), i))
-            {
-                if record.timestamp >= start && record.timestamp <= end {
-                    records.push_back(record);
-                }
+        let mut page = RecordPage {
+            records: vec![&e],
+            next_cursor: None,
+        };
+        for i in cursor..end {
+            let record = Self::load_record(&e, &owner, i);
+            if Self::grant_covers(&grant, record.record_type, record.timestamp) {
+                page.records.push_back(record);
             }
         }
-        records
+        if end < count {
+            page.next_cursor = Some(end);
+        }
+        page
     }
 
-    /// Retrieves records of a specific type.
-    pub fn get_records_by_type(
+    /// Retrieves a page of records within a specific date range, using the
+    /// owner's per-month index. Paging works as in `get_financial_records`;
+    /// at most `MAX_MONTHS_PER_PAGE` months are scanned per call, so a page
+    /// may be short or empty and still carry a cursor.
+    pub fn get_records_by_date_range(
         e: Env,
         caller: Address,
         owner: Address,
-        record_type: RecordType,
-    ) -> Vec<FinancialRecord> {
-        Self::check_access(&e, &caller, &owner);
+        start: u64,
+        end: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> RecordPage {
+        let grant = Self::check_access(&e, &caller, &owner);
 
+        let mut page = RecordPage {
+            records: vec![&e],
+            next_cursor: None,
+        };
         let count: u32 = e
             .storage()
             .persistent()
             .get(&DataKey::RecordCount(owner.clone()))
             .unwrap_or(0);
-        let mut records = vec![&e];
+        if cursor >= count || start > end {
+            return page;
+        }
+
+        // Records are appended in time order, so no month before the cursor's
+        // record or after the current ledger time can hold a match
+        let first = Self::load_record(&e, &owner, cursor).timestamp.max(start);
+        let last = e.ledger().timestamp().min(end);
+        if first > last {
+            return page;
+        }
+
+        let mut budget = limit.min(MAX_PAGE_SIZE);
+        let mut month = Self::month_of(first);
+        let last_month = Self::month_of(last);
+        let mut months_left = MAX_MONTHS_PER_PAGE;
+        while month <= last_month {
+            if months_left == 0 {
+                // Resume at the first record from this month onwards
+                let next =
+                    Self::first_record_from(&e, &owner, cursor, count, Self::month_start(month));
+                if next < count && Self::load_record(&e, &owner, next).timestamp <= last {
+                    page.next_cursor = Some(next);
+                }
+                return page;
+            }
+            months_left -= 1;
 
-        for i in 0..count {
-            if let Some(record) = e
+            let ids: Vec<u32> = e
                 .storage()
                 .persistent()
-                .get::<DataKey, FinancialRecord>(&DataKey::Record(owner.clone(), i))
-            {
-                if record.record_type == record_type {
-                    records.push_back(record);
-                }
+                .get(&DataKey::MonthIndex(owner.clone(), month))
+                .unwrap_or(vec![&e]);
+            let from = Self::lower_bound(&ids, cursor);
+            if Self::fill_page(
+                &e,
+                &owner,
+                &grant,
+                &ids,
+                from,
+                start,
+                end,
+                &mut budget,
+                &mut page,
+            ) {
+                return page;
             }
+            month = Self::next_month(month);
         }
-        records
-    }
-
-    /// Grants access to another address.
-    pub fn grant_access(e: Env, owner: Address, authorized: Address) {
-        owner.require_auth();
-        e.storage()
-            .persistent()
-            .set(&DataKey::Access(owner, authorized), &true);
+        page
     }
 
-    /// Revokes access from another address.
+    /// Retrieves a page of records of a specific type, using the owner's
+    /// per-type index. Paging works as in `get_financial_records`.
+    pub fn get_records_by_type(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        record_type: RecordType,
+        cursor: u32,
+        limit: u32,
+    ) -> RecordPage {
+        Self::get_records_by_type_and_range(
+            e,
+            caller,
+            owner,
+            record_type,
+            0,
+            u64::MAX,
+            cursor,
+            limit,
+        )
+    }
+
+    /// Retrieves a page of records of a specific type within a date range.
+    /// Paging works as in `get_financial_records`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_records_by_type_and_range(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        record_type: RecordType,
+        start: u64,
+        end: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> RecordPage {
+        let grant = Self::check_access(&e, &caller, &owner);
+
+        let mut page = RecordPage {
+            records: vec![&e],
+            next_cursor: None,
+        };
+        if start > end {
+            return page;
+        }
+
+        let ids: Vec<u32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::TypeIndex(owner.clone(), record_type))
+            .unwrap_or(vec![&e]);
+
+        // Binary search for the first record on or after `start`
+        let mut lo = Self::lower_bound(&ids, cursor);
+        let mut hi = ids.len();
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let record = Self::load_record(&e, &owner, ids.get_unchecked(mid));
+            if record.timestamp < start {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let mut budget = limit.min(MAX_PAGE_SIZE);
+        Self::fill_page(
+            &e,
+            &owner,
+            &grant,
+            &ids,
+            lo,
+            start,
+            end,
+            &mut budget,
+            &mut page,
+        );
+        page
+    }
+
+    /// Grants another address read access to records of the given types
+    /// (all types if empty) dated between `start` and `end`, until `expires_at`.
+    /// Granting again replaces the previous grant.
+    pub fn grant_access(
+        e: Env,
+        owner: Address,
+        authorized: Address,
+        record_types: Vec<RecordType>,
+        start: u64,
+        end: u64,
+        expires_at: u64,
+    ) {
+        owner.require_auth();
+
+        if start > end {
+            panic!("Invalid date range");
+        }
+        let now = e.ledger().timestamp();
+        if expires_at <= now {
+            panic!("Invalid expiry");
+        }
+
+        let grant = AccessGrant {
+            grantee: authorized.clone(),
+            record_types,
+            start,
+            end,
+            expires_at,
+            granted_at: now,
+        };
+        e.storage()
+            .persistent()
+            .set(&DataKey::Access(owner.clone(), authorized.clone()), &grant);
+
+        let grantees_key = DataKey::Grantees(owner);
+        let mut grantees: Vec<Address> = e
+            .storage()
+            .persistent()
+            .get(&grantees_key)
+            .unwrap_or(vec![&e]);
+        if !grantees.contains(&authorized) {
+            grantees.push_back(authorized);
+            e.storage().persistent().set(&grantees_key, &grantees);
+        }
+    }
+
+    /// Revokes access from another address.
     pub fn revoke_access(e: Env, owner: Address, authorized: Address) {
         owner.require_auth();
         e.storage()
             .persistent()
-            .remove(&DataKey::Access(owner, authorized));
+            .remove(&DataKey::Access(owner.clone(), authorized.clone()));
+
+        let grantees_key = DataKey::Grantees(owner);
+        let mut grantees: Vec<Address> = e
+            .storage()
+            .persistent()
+            .get(&grantees_key)
+            .unwrap_or(vec![&e]);
+        if let Some(index) = grantees.first_index_of(&authorized) {
+            grantees.remove(index);
+            e.storage().persistent().set(&grantees_key, &grantees);
+        }
+    }
+
+    /// Lists the owner's grants that have not yet expired.
+    pub fn get_active_grants(e: Env, owner: Address) -> Vec<AccessGrant> {
+        owner.require_auth();
+
+        let grantees: Vec<Address> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Grantees(owner.clone()))
+            .unwrap_or(vec![&e]);
+        let now = e.ledger().timestamp();
+        let mut grants = vec![&e];
+        for grantee in grantees.iter() {
+            if let Some(grant) = e
+                .storage()
+                .persistent()
+                .get::<DataKey, AccessGrant>(&DataKey::Access(owner.clone(), grantee))
+            {
+                if grant.expires_at > now {
+                    grants.push_back(grant);
+                }
+            }
+        }
+        grants
+    }
+
+    /// Sets the token an invoice can be paid in on-chain with `pay_invoice`.
+    /// The token can only be changed before any payment has been made.
+    pub fn set_invoice_payment_token(e: Env, issuer: Address, invoice_id: u64, token: Address) {
+        issuer.require_auth();
+
+        let mut invoice = Self::load_invoice(&e, invoice_id);
+        if issuer != invoice.issuer {
+            panic!("Only the invoice issuer can set the payment token");
+        }
+        if invoice.status != InvoiceStatus::Issued || invoice.amount_paid != 0 {
+            panic!("Invoice is not payable");
+        }
+
+        invoice.payment_token = Some(token);
+        invoice.updated_at = e.ledger().timestamp();
+        e.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &invoice);
+    }
+
+    /// Pays towards an invoice by transferring its payment token from the payer
+    /// to the issuer. Partial payments are allowed up to the outstanding balance.
+    /// A linked `Receipt` record is added for both parties.
+    pub fn pay_invoice(e: Env, payer: Address, invoice_id: u64, amount: i128) -> u64 {
+        payer.require_auth();
+
+        let invoice = Self::load_invoice(&e, invoice_id);
+        let payment_token = Self::check_payment(&invoice, &payer, amount);
+        token::Client::new(&e, &payment_token).transfer(&payer, &invoice.issuer, &amount);
+
+        Self::record_payment(&e, invoice, payer, amount, false)
+    }
+
+    /// Refunds part or all of the amount paid on an invoice from the issuer
+    /// back to the payer. Amounts released from the payer's vault are refunded
+    /// into the vault first and free up the provider's spending cap again.
+    /// A linked `Receipt` record is added for both parties.
+    pub fn refund_invoice(e: Env, issuer: Address, invoice_id: u64, amount: i128) -> u64 {
+        issuer.require_auth();
+
+        let mut invoice = Self::load_invoice(&e, invoice_id);
+        if issuer != invoice.issuer {
+            panic!("Only the invoice issuer can refund");
+        }
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+        if amount > invoice.amount_paid {
+            panic!("Refund exceeds amount paid");
+        }
+
+        let payment_token = invoice
+            .payment_token
+            .clone()
+            .expect("Invoice has no payment token");
+        let vault_paid = Self::vault_paid(&e, invoice_id);
+        let via_vault = vault_paid > 0;
+        if via_vault {
+            if amount > vault_paid {
+                panic!("Refund exceeds vault payment");
+            }
+            token::Client::new(&e, &payment_token).transfer(
+                &issuer,
+                e.current_contract_address(),
+                &amount,
+            );
+
+            let mut vault = Self::get_vault(e.clone(), invoice.payer.clone());
+            vault.balance += amount;
+            vault.total_released -= amount;
+            e.storage()
+                .persistent()
+                .set(&DataKey::Vault(invoice.payer.clone()), &vault);
+            let spent = Self::get_provider_spent(e.clone(), invoice.payer.clone(), issuer.clone());
+            e.storage().persistent().set(
+                &DataKey::ProviderSpent(invoice.payer.clone(), issuer.clone()),
+                &(spent - amount),
+            );
+        } else {
+            token::Client::new(&e, &payment_token).transfer(&issuer, &invoice.payer, &amount);
+        }
+
+        let outstanding_before = Self::outstanding(&invoice);
+        invoice.amount_paid -= amount;
+        if invoice.status == InvoiceStatus::Paid || invoice.status == InvoiceStatus::PartiallyPaid {
+            invoice.status = if invoice.amount_paid == 0 {
+                InvoiceStatus::Issued
+            } else {
+                InvoiceStatus::PartiallyPaid
+            };
+        }
+        Self::settle(
+            &e,
+            invoice,
+            outstanding_before,
+            issuer.clone(),
+            amount,
+            true,
+            via_vault,
+        )
+    }
+
+    /// Retrieves the payments and refunds made on an invoice.
+    pub fn get_invoice_payments(e: Env, caller: Address, invoice_id: u64) -> Vec<Payment> {
+        let invoice = Self::get_invoice(e.clone(), caller, invoice_id);
+
+        let ids: Vec<u64> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::InvoicePayments(invoice.invoice_id))
+            .unwrap_or(vec![&e]);
+        let mut payments = vec![&e];
+        for id in ids.iter() {
+            if let Some(payment) = e.storage().persistent().get(&DataKey::Payment(id)) {
+                payments.push_back(payment);
+            }
+        }
+        payments
+    }
+
+    /// Sets the admin who maintains eligible services and registered providers
+    /// when the contract is deployed, so that no later caller can claim the role.
+    pub fn __constructor(e: Env, admin: Address) {
+        e.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    /// Adds a service code to the list of HSA-eligible services (admin only).
+    pub fn add_eligible_service(e: Env, admin: Address, service_code: String) {
+        Self::require_admin(&e, &admin);
+        e.storage()
+            .persistent()
+            .set(&DataKey::EligibleService(service_code), &true);
+    }
+
+    /// Removes a service code from the list of HSA-eligible services (admin only).
+    pub fn remove_eligible_service(e: Env, admin: Address, service_code: String) {
+        Self::require_admin(&e, &admin);
+        e.storage()
+            .persistent()
+            .remove(&DataKey::EligibleService(service_code));
+    }
+
+    /// Checks whether a service code is HSA-eligible.
+    pub fn is_eligible_service(e: Env, service_code: String) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::EligibleService(service_code))
+    }
+
+    /// Registers a provider that HSA vaults may pay (admin only).
+    pub fn register_provider(e: Env, admin: Address, provider: Address) {
+        Self::require_admin(&e, &admin);
+        e.storage()
+            .persistent()
+            .set(&DataKey::Provider(provider), &true);
+    }
+
+    /// Removes a provider from the registered list (admin only).
+    pub fn remove_provider(e: Env, admin: Address, provider: Address) {
+        Self::require_admin(&e, &admin);
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Provider(provider));
+    }
+
+    /// Checks whether a provider is registered.
+    pub fn is_registered_provider(e: Env, provider: Address) -> bool {
+        e.storage().persistent().has(&DataKey::Provider(provider))
+    }
+
+    /// Opens the owner's HSA vault for a token.
+    pub fn open_vault(e: Env, owner: Address, token: Address) {
+        owner.require_auth();
+
+        let key = DataKey::Vault(owner.clone());
+        if e.storage().persistent().has(&key) {
+            panic!("Vault already exists");
+        }
+        let vault = HsaVault {
+            owner,
+            token,
+            balance: 0,
+            total_deposited: 0,
+            total_released: 0,
+        };
+        e.storage().persistent().set(&key, &vault);
+    }
+
+    /// Deposits tokens from the owner into their HSA vault.
+    pub fn deposit_to_vault(e: Env, owner: Address, amount: i128) {
+        owner.require_auth();
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+
+        let mut vault = Self::get_vault(e.clone(), owner.clone());
+        token::Client::new(&e, &vault.token).transfer(
+            &owner,
+            e.current_contract_address(),
+            &amount,
+        );
+
+        vault.balance += amount;
+        vault.total_deposited += amount;
+        e.storage().persistent().set(&DataKey::Vault(owner), &vault);
+    }
+
+    /// Retrieves the owner's HSA vault.
+    pub fn get_vault(e: Env, owner: Address) -> HsaVault {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Vault(owner))
+            .expect("Vault not found")
+    }
+
+    /// Sets (or clears, with `None`) the total the owner's vault may release to a provider.
+    pub fn set_provider_cap(e: Env, owner: Address, provider: Address, cap: Option<i128>) {
+        owner.require_auth();
+
+        let key = DataKey::ProviderCap(owner, provider);
+        match cap {
+            Some(cap) if cap < 0 => panic!("Invalid amount"),
+            Some(cap) => e.storage().persistent().set(&key, &cap),
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Retrieves the owner's spending cap for a provider, if any.
+    pub fn get_provider_cap(e: Env, owner: Address, provider: Address) -> Option<i128> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::ProviderCap(owner, provider))
+    }
+
+    /// Retrieves the total the owner's vault has released to a provider.
+    pub fn get_provider_spent(e: Env, owner: Address, provider: Address) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::ProviderSpent(owner, provider))
+            .unwrap_or(0)
+    }
+
+    /// Pays an invoice from the payer's HSA vault. The issuer must be a
+    /// registered provider, every line item must be an eligible service and the
+    /// release must stay within the payer's cap for that provider. The payment
+    /// is recorded like `pay_invoice`, with a linked `Receipt` for both parties.
+    pub fn release_from_vault(e: Env, owner: Address, invoice_id: u64, amount: i128) -> u64 {
+        owner.require_auth();
+
+        let mut vault = Self::get_vault(e.clone(), owner.clone());
+        let invoice = Self::load_invoice(&e, invoice_id);
+        let payment_token = Self::check_payment(&invoice, &owner, amount);
+        if payment_token != vault.token {
+            panic!("Invoice is not payable in the vault token");
+        }
+        if !Self::is_registered_provider(e.clone(), invoice.issuer.clone()) {
+            panic!("Provider not registered");
+        }
+        for item in invoice.line_items.iter() {
+            if !Self::is_eligible_service(e.clone(), item.service_code) {
+                panic!("Invoice contains ineligible services");
+            }
+        }
+        if amount > vault.balance {
+            panic!("Insufficient vault balance");
+        }
+
+        let spent = Self::get_provider_spent(e.clone(), owner.clone(), invoice.issuer.clone());
+        if let Some(cap) = Self::get_provider_cap(e.clone(), owner.clone(), invoice.issuer.clone())
+        {
+            if spent + amount > cap {
+                panic!("Provider spending cap exceeded");
+            }
+        }
+
+        token::Client::new(&e, &vault.token).transfer(
+            &e.current_contract_address(),
+            &invoice.issuer,
+            &amount,
+        );
+
+        vault.balance -= amount;
+        vault.total_released += amount;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Vault(owner.clone()), &vault);
+        e.storage().persistent().set(
+            &DataKey::ProviderSpent(owner.clone(), invoice.issuer.clone()),
+            &(spent + amount),
+        );
+
+        Self::record_payment(&e, invoice, owner, amount, true)
+    }
+
+    /// Internal helper to validate a payment and return the invoice's payment token.
+    fn check_payment(invoice: &Invoice, payer: &Address, amount: i128) -> Address {
+        if *payer != invoice.payer {
+            panic!("Only the invoice payer can pay");
+        }
+        if invoice.status != InvoiceStatus::Issued && invoice.status != InvoiceStatus::PartiallyPaid
+        {
+            panic!("Invoice is not payable");
+        }
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+        if amount > invoice.total - invoice.amount_paid {
+            panic!("Payment exceeds invoice balance");
+        }
+
+        invoice
+            .payment_token
+            .clone()
+            .expect("Invoice has no payment token")
+    }
+
+    /// Internal helper to apply a transferred payment to an invoice.
+    fn record_payment(
+        e: &Env,
+        mut invoice: Invoice,
+        payer: Address,
+        amount: i128,
+        via_vault: bool,
+    ) -> u64 {
+        let outstanding_before = Self::outstanding(&invoice);
+        invoice.amount_paid += amount;
+        invoice.status = if invoice.amount_paid == invoice.total {
+            InvoiceStatus::Paid
+        } else {
+            InvoiceStatus::PartiallyPaid
+        };
+        Self::settle(
+            e,
+            invoice,
+            outstanding_before,
+            payer,
+            amount,
+            false,
+            via_vault,
+        )
+    }
+
+    /// Internal helper returning the amount paid on an invoice from the payer's
+    /// vault that has not been refunded yet.
+    fn vault_paid(e: &Env, invoice_id: u64) -> i128 {
+        let ids: Vec<u64> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::InvoicePayments(invoice_id))
+            .unwrap_or(vec![e]);
+        let mut paid = 0;
+        for id in ids.iter() {
+            let payment: Payment = e.storage().persistent().get(&DataKey::Payment(id)).unwrap();
+            if payment.via_vault {
+                paid += if payment.is_refund {
+                    -payment.amount
+                } else {
+                    payment.amount
+                };
+            }
+        }
+        paid
+    }
+
+    /// Internal helper to save an invoice after a token transfer, record the
+    /// payment with a `Receipt` for each party and update both parties' totals.
+    fn settle(
+        e: &Env,
+        mut invoice: Invoice,
+        outstanding_before: i128,
+        from: Address,
+        amount: i128,
+        is_refund: bool,
+        via_vault: bool,
+    ) -> u64 {
+        let now = e.ledger().timestamp();
+        invoice.updated_at = now;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice.invoice_id), &invoice);
+        Self::adjust_outstanding(
+            e,
+            &invoice,
+            Self::outstanding(&invoice) - outstanding_before,
+        );
+
+        let net = if is_refund { -amount } else { amount };
+        Self::sync_plan(e, &invoice, net);
+        let year = Self::month_of(now) / 100;
+        let mut payer_summary =
+            Self::load_summary(e, &invoice.payer, year, invoice.currency.clone());
+        payer_summary.medical_expenses_paid += net;
+        Self::save_summary(e, &invoice.payer, &payer_summary);
+        let mut issuer_summary =
+            Self::load_summary(e, &invoice.issuer, year, invoice.currency.clone());
+        issuer_summary.payments_received += net;
+        Self::save_summary(e, &invoice.issuer, &issuer_summary);
+
+        let to = if is_refund {
+            invoice.payer.clone()
+        } else {
+            invoice.issuer.clone()
+        };
+        let payment_id: u64 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::PaymentCount)
+            .unwrap_or(0)
+            + 1;
+        let payment = Payment {
+            payment_id,
+            invoice_id: invoice.invoice_id,
+            from,
+            to,
+            amount,
+            is_refund,
+            via_vault,
+            timestamp: now,
+        };
+        e.storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id), &payment);
+        e.storage()
+            .persistent()
+            .set(&DataKey::PaymentCount, &payment_id);
+
+        let payments_key = DataKey::InvoicePayments(invoice.invoice_id);
+        let mut payment_ids: Vec<u64> = e
+            .storage()
+            .persistent()
+            .get(&payments_key)
+            .unwrap_or(vec![&e]);
+        payment_ids.push_back(payment_id);
+        e.storage().persistent().set(&payments_key, &payment_ids);
+
+        let description = if is_refund {
+            String::from_str(e, "Invoice refund")
+        } else {
+            String::from_str(e, "Invoice payment")
+        };
+        for party in [invoice.payer, invoice.issuer] {
+            let mut record = Self::new_record(
+                e,
+                &party,
+                RecordType::Receipt,
+                String::from_str(e, ""),
+                description.clone(),
+                Money {
+                    amount: net,
+                    currency: invoice.currency.clone(),
+                },
+            );
+            record.invoice_id = Some(invoice.invoice_id);
+            Self::store_record(e, record);
+        }
+
+        payment_id
+    }
+
+    /// Internal helper to build an original record, ready for `store_record`.
+    fn new_record(
+        e: &Env,
+        owner: &Address,
+        record_type: RecordType,
+        ipfs_hash: String,
+        description: String,
+        amount: Money,
+    ) -> FinancialRecord {
+        FinancialRecord {
+            owner: owner.clone(),
+            index: 0,
+            record_type,
+            ipfs_hash,
+            timestamp: 0,
+            description,
+            amount,
+            tax_year: 0,
+            invoice_id: None,
+            entry_kind: EntryKind::Original,
+            amends: None,
+            prev_hash: BytesN::from_array(e, &[0; 32]),
+        }
+    }
+
+    /// Internal helper to append a record to the owner's list and hash chain,
+    /// returning its index.
+    fn store_record(e: &Env, mut record: FinancialRecord) -> u32 {
+        let owner = record.owner.clone();
+        let count: u32 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::RecordCount(owner.clone()))
+            .unwrap_or(0);
+
+        record.index = count;
+        record.timestamp = e.ledger().timestamp();
+        if record.entry_kind == EntryKind::Original {
+            record.tax_year = Self::month_of(record.timestamp) / 100;
+        }
+        if let Some(head) = e
+            .storage()
+            .persistent()
+            .get(&DataKey::ChainHead(owner.clone()))
+        {
+            record.prev_hash = head;
+        }
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::Record(owner.clone(), count), &record);
+        e.storage()
+            .persistent()
+            .set(&DataKey::RecordCount(owner.clone()), &(count + 1));
+        e.storage().persistent().set(
+            &DataKey::ChainHead(owner.clone()),
+            &Self::record_hash(e, &record),
+        );
+
+        Self::add_to_index(
+            e,
+            DataKey::TypeIndex(owner.clone(), record.record_type),
+            count,
+        );
+        Self::add_to_index(
+            e,
+            DataKey::MonthIndex(owner, Self::month_of(record.timestamp)),
+            count,
+        );
+        if record.entry_kind != EntryKind::Void {
+            Self::apply_to_summary(e, &record, 1);
+            Self::add_to_index(
+                e,
+                DataKey::YearIndex(record.owner.clone(), record.tax_year),
+                count,
+            );
+        }
+        count
+    }
+
+    /// Internal helper to add (`sign` = 1) or remove (`sign` = -1) a record's
+    /// contribution to its tax year summary. Invoice-linked records only count
+    /// by type; their payments are categorised when settled.
+    fn apply_to_summary(e: &Env, record: &FinancialRecord, sign: i32) {
+        let mut summary = Self::load_summary(
+            e,
+            &record.owner,
+            record.tax_year,
+            record.amount.currency.clone(),
+        );
+        let count = summary.record_counts.get(record.record_type).unwrap_or(0);
+        let total = summary.record_totals.get(record.record_type).unwrap_or(0);
+        let amount = record.amount.amount * sign as i128;
+        summary
+            .record_counts
+            .set(record.record_type, count.saturating_add_signed(sign));
+        summary
+            .record_totals
+            .set(record.record_type, total + amount);
+
+        if record.invoice_id.is_none() {
+            match record.record_type {
+                RecordType::Receipt => summary.medical_expenses_paid += amount,
+                RecordType::Reimbursement => summary.reimbursements_received += amount,
+                _ => {}
+            }
+        }
+        Self::save_summary(e, &record.owner, &summary);
+    }
+
+    fn load_summary(e: &Env, owner: &Address, year: u32, currency: String) -> TaxYearSummary {
+        e.storage()
+            .persistent()
+            .get(&DataKey::TaxYear(owner.clone(), year, currency.clone()))
+            .unwrap_or(TaxYearSummary {
+                year,
+                currency,
+                record_counts: Map::new(e),
+                record_totals: Map::new(e),
+                medical_expenses_paid: 0,
+                reimbursements_received: 0,
+                payments_received: 0,
+                outstanding_invoices: 0,
+            })
+    }
+
+    fn save_summary(e: &Env, owner: &Address, summary: &TaxYearSummary) {
+        e.storage().persistent().set(
+            &DataKey::TaxYear(owner.clone(), summary.year, summary.currency.clone()),
+            summary,
+        );
+    }
+
+    /// Internal helper to require access to every record behind a summary:
+    /// a grant must cover all record types and the whole tax year.
+    fn check_summary_access(e: &Env, caller: &Address, owner: &Address, year: u32) {
+        if let Some(grant) = Self::check_access(e, caller, owner) {
+            let year_start = Self::month_start(year * 100 + 1);
+            let year_end = Self::month_start((year + 1) * 100 + 1) - 1;
+            if !grant.record_types.is_empty() || grant.start > year_start || grant.end < year_end {
+                panic!("Access denied");
+            }
+        }
+    }
+
+    /// Internal helper to require a three-letter upper-case ISO 4217 code.
+    fn check_currency(code: &String) {
+        if code.len() != 3 {
+            panic!("Invalid currency code");
+        }
+        let mut letters = [0u8; 3];
+        code.copy_into_slice(&mut letters);
+        if !letters.iter().all(|c| c.is_ascii_uppercase()) {
+            panic!("Invalid currency code");
+        }
+    }
+
+    fn check_money(money: &Money) {
+        if money.amount < 0 {
+            panic!("Invalid amount");
+        }
+        Self::check_currency(&money.currency);
+    }
+
+    /// Internal helper to find the position after the last snapshot at or before `timestamp`.
+    fn rate_position(rates: &Vec<RateSnapshot>, timestamp: u64) -> u32 {
+        let mut lo = 0;
+        let mut hi = rates.len();
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if rates.get_unchecked(mid).timestamp <= timestamp {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    fn rate_at(e: &Env, base: &String, quote: &String, timestamp: u64) -> Option<i128> {
+        let rates: Vec<RateSnapshot> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Rates(base.clone(), quote.clone()))?;
+        match Self::rate_position(&rates, timestamp) {
+            0 => None,
+            pos => Some(rates.get_unchecked(pos - 1).rate),
+        }
+    }
+
+    fn convert(e: &Env, money: &Money, currency: &String, timestamp: u64) -> i128 {
+        let rate = Self::get_exchange_rate(
+            e.clone(),
+            money.currency.clone(),
+            currency.clone(),
+            timestamp,
+        );
+        money.amount * rate / RATE_SCALE
+    }
+
+    fn load_plan(e: &Env, plan_id: u64) -> PaymentPlan {
+        e.storage()
+            .persistent()
+            .get(&DataKey::PaymentPlan(plan_id))
+            .expect("Payment plan not found")
+    }
+
+    /// Internal helper for the number of installments whose due date has
+    /// passed without being covered by payments.
+    fn missed_installments(e: &Env, plan: &PaymentPlan) -> u32 {
+        let now = e.ledger().timestamp();
+        if now <= plan.start_date {
+            return 0;
+        }
+        let elapsed = (now - plan.start_date - 1) / plan.frequency + 1;
+        let past_due = elapsed.min(plan.installment_count as u64) as u32;
+        past_due.saturating_sub(plan.installments_paid)
+    }
+
+    /// Internal helper to bring an invoice's active payment plan in line with
+    /// a payment (`paid_delta`, negative for refunds) or a status change.
+    fn sync_plan(e: &Env, invoice: &Invoice, paid_delta: i128) {
+        let plan_id: Option<u64> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::InvoicePlan(invoice.invoice_id));
+        let mut plan = match plan_id {
+            Some(plan_id) => Self::load_plan(e, plan_id),
+            None => return,
+        };
+        if plan.status != PlanStatus::Active && plan.status != PlanStatus::Delinquent {
+            return;
+        }
+
+        plan.amount_paid += paid_delta;
+        plan.installments_paid = ((plan.amount_paid.max(0) / plan.installment_amount) as u32)
+            .min(plan.installment_count);
+        if invoice.status == InvoiceStatus::Void {
+            plan.status = PlanStatus::Cancelled;
+        } else if invoice.status == InvoiceStatus::Paid || plan.amount_paid >= plan.principal {
+            plan.status = PlanStatus::Completed;
+            plan.installments_paid = plan.installment_count;
+        } else {
+            plan.missed_installments = Self::missed_installments(e, &plan);
+            plan.status = if plan.missed_installments > 0 {
+                PlanStatus::Delinquent
+            } else {
+                PlanStatus::Active
+            };
+        }
+
+        plan.next_due_date =
+            if plan.status == PlanStatus::Completed || plan.status == PlanStatus::Cancelled {
+                0
+            } else {
+                plan.start_date + plan.installments_paid as u64 * plan.frequency
+            };
+        e.storage()
+            .persistent()
+            .set(&DataKey::PaymentPlan(plan.plan_id), &plan);
+    }
+
+    /// Internal helper for the unpaid balance of an invoice that is still owed.
+    fn outstanding(invoice: &Invoice) -> i128 {
+        match invoice.status {
+            InvoiceStatus::Paid | InvoiceStatus::Void => 0,
+            _ => invoice.total - invoice.amount_paid,
+        }
+    }
+
+    /// Internal helper to move both parties' outstanding totals for the
+    /// invoice's year of issue.
+    fn adjust_outstanding(e: &Env, invoice: &Invoice, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let year = Self::month_of(invoice.issued_at) / 100;
+        for party in [&invoice.issuer, &invoice.payer] {
+            let mut summary = Self::load_summary(e, party, year, invoice.currency.clone());
+            summary.outstanding_invoices += delta;
+            Self::save_summary(e, party, &summary);
+        }
+    }
+
+    fn record_hash(e: &Env, record: &FinancialRecord) -> BytesN<32> {
+        e.crypto().sha256(&record.clone().to_xdr(e)).into()
+    }
+
+    /// Internal helper to load a record that may still be amended or voided.
+    fn load_correctable(e: &Env, owner: &Address, index: u32) -> FinancialRecord {
+        let record = Self::load_record(e, owner, index);
+        if e.storage()
+            .persistent()
+            .has(&DataKey::SupersededBy(owner.clone(), index))
+        {
+            panic!("Record already amended or voided");
+        }
+        if record.entry_kind == EntryKind::Void {
+            panic!("Void entries cannot be changed");
+        }
+        if record.invoice_id.is_some() {
+            panic!("Invoice records are managed through the invoice");
+        }
+        record
+    }
+
+    fn supersede(e: &Env, original: FinancialRecord, record: FinancialRecord) -> u32 {
+        let entry = Self::store_record(e, record);
+        e.storage().persistent().set(
+            &DataKey::SupersededBy(original.owner.clone(), original.index),
+            &entry,
+        );
+        Self::apply_to_summary(e, &original, -1);
+        entry
+    }
+
+    fn add_to_index(e: &Env, key: DataKey, index: u32) {
+        let mut ids: Vec<u32> = e.storage().persistent().get(&key).unwrap_or(vec![e]);
+        ids.push_back(index);
+        e.storage().persistent().set(&key, &ids);
+    }
+
+    fn load_record(e: &Env, owner: &Address, index: u32) -> FinancialRecord {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Record(owner.clone(), index))
+            .expect("Record not found")
+    }
+
+    /// Internal helper to find the first position in a sorted index holding
+    /// a record index of at least `cursor`.
+    fn lower_bound(ids: &Vec<u32>, cursor: u32) -> u32 {
+        let mut lo = 0;
+        let mut hi = ids.len();
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if ids.get_unchecked(mid) < cursor {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Internal helper to add records from `ids[from..]` dated within
+    /// `start..=end` to the page, examining at most `budget` records.
+    /// Returns true once the page is complete.
+    #[allow(clippy::too_many_arguments)]
+    fn fill_page(
+        e: &Env,
+        owner: &Address,
+        grant: &Option<AccessGrant>,
+        ids: &Vec<u32>,
+        from: u32,
+        start: u64,
+        end: u64,
+        budget: &mut u32,
+        page: &mut RecordPage,
+    ) -> bool {
+        for pos in from..ids.len() {
+            let index = ids.get_unchecked(pos);
+            if *budget == 0 {
+                page.next_cursor = Some(index);
+                return true;
+            }
+            let record = Self::load_record(e, owner, index);
+            if record.timestamp > end {
+                return true;
+            }
+            *budget -= 1;
+            if record.timestamp >= start
+                && Self::grant_covers(grant, record.record_type, record.timestamp)
+            {
+                page.records.push_back(record);
+            }
+        }
+        false
+    }
+
+    /// Internal helper to convert a unix timestamp into a YYYYMM month key.
+    fn month_of(timestamp: u64) -> u32 {
+        // Civil-from-days conversion (proleptic Gregorian calendar)
+        let days = (timestamp / 86_400) as i64 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (year * 100 + month) as u32
+    }
+
+    fn month_start(month: u32) -> u64 {
+        // Days-from-civil conversion, the inverse of `month_of`
+        let month_of_year = (month % 100) as i64;
+        let year = (month / 100) as i64 - if month_of_year <= 2 { 1 } else { 0 };
+        let era = year / 400;
+        let year_of_era = year - era * 400;
+        let mp = if month_of_year > 2 {
+            month_of_year - 3
+        } else {
+            month_of_year + 9
+        };
+        let day_of_year = (153 * mp + 2) / 5;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        (era * 146_097 + day_of_era - 719_468) as u64 * 86_400
+    }
+
+    /// Internal helper for the first record index in `lo..hi` dated at or
+    /// after `timestamp`, relying on records being appended in time order.
+    fn first_record_from(e: &Env, owner: &Address, lo: u32, hi: u32, timestamp: u64) -> u32 {
+        let mut lo = lo;
+        let mut hi = hi;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if Self::load_record(e, owner, mid).timestamp < timestamp {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    fn next_month(month: u32) -> u32 {
+        if month % 100 == 12 {
+            (month / 100 + 1) * 100 + 1
+        } else {
+            month + 1
+        }
+    }
+
+    fn load_invoice(e: &Env, invoice_id: u64) -> Invoice {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Invoice(invoice_id))
+            .expect("Invoice not found")
+    }
+
+    fn require_admin(e: &Env, admin: &Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *admin != stored_admin {
+            panic!("Only admin can perform this action");
+        }
     }
 
     /// Internal helper to check access.
-    fn check_access(e: &Env, caller: &Address, owner: &Address) {
+    /// Returns the caller's grant, or `None` when the caller is the owner.
+    fn check_access(e: &Env, caller: &Address, owner: &Address) -> Option<AccessGrant> {
+        caller.require_auth();
         if caller == owner {
-            return;
+            return None;
         }
-        let is_authorized: bool = e
+        let grant: AccessGrant = e
             .storage()
             .persistent()
             .get(&DataKey::Access(owner.clone(), caller.clone()))
-            .unwrap_or(false);
+            .expect("Access denied");
 
-        if !is_authorized {
+        if grant.expires_at <= e.ledger().timestamp() {
             panic!("Access denied");
         }
+        Some(grant)
+    }
+
+    /// Internal helper to check whether a grant covers a record.
+    fn grant_covers(grant: &Option<AccessGrant>, record_type: RecordType, timestamp: u64) -> bool {
+        match grant {
+            None => true,
+            Some(grant) => {
+                (grant.record_types.is_empty() || grant.record_types.contains(record_type))
+                    && timestamp >= grant.start
+                    && timestamp <= grant.end
+            }
+        }
     }
 }
 
@@ -1,23 +1,31 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::Env;
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{token::StellarAssetClient, Env};
 
 #[test]
 fn test_add_and_get_records() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let contract_id = e.register(FinancialRecordContract, ());
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
     let client = FinancialRecordContractClient::new(&e, &contract_id);
 
     let owner = Address::generate(&e);
     let ipfs_hash = String::from_str(&e, "QmXoypizj2Madv6NthR75ce451F33968F9e1XF3D8xS288");
     let description = String::from_str(&e, "Test Tax Document");
 
-    client.add_financial_record(&owner, &RecordType::TaxDocument, &ipfs_hash, &description);
+    client.add_financial_record(
+        &owner,
+        &RecordType::TaxDocument,
+        &ipfs_hash,
+        &description,
+        &usd(&e, 0),
+    );
 
-    let records = client.get_financial_records(&owner, &owner);
+    let records = client
+        .get_financial_records(&owner, &owner, &0, &50)
+        .records;
     assert_eq!(records.len(), 1);
     let record = records.get(0).unwrap();
     assert_eq!(record.owner, owner);
@@ -31,7 +39,7 @@ fn test_access_granted() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let contract_id = e.register(FinancialRecordContract, ());
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
     let client = FinancialRecordContractClient::new(&e, &contract_id);
 
     let owner = Address::generate(&e);
@@ -40,13 +48,21 @@ fn test_access_granted() {
     let ipfs_hash = String::from_str(&e, "hash");
     let description = String::from_str(&e, "desc");
 
-    client.add_financial_record(&owner, &RecordType::Invoice, &ipfs_hash, &description);
+    client.add_financial_record(
+        &owner,
+        &RecordType::Invoice,
+        &ipfs_hash,
+        &description,
+        &usd(&e, 0),
+    );
 
     // Grant access
-    client.grant_access(&owner, &auditor);
+    client.grant_access(&owner, &auditor, &vec![&e], &0, &u64::MAX, &1_000);
 
     // Auditor can see now
-    let records = client.get_financial_records(&auditor, &owner);
+    let records = client
+        .get_financial_records(&auditor, &owner, &0, &50)
+        .records;
     assert_eq!(records.len(), 1);
 }
 
@@ -56,7 +72,7 @@ fn test_unauthorized_access() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let contract_id = e.register(FinancialRecordContract, ());
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
     let client = FinancialRecordContractClient::new(&e, &contract_id);
 
     let owner = Address::generate(&e);
@@ -67,10 +83,11 @@ fn test_unauthorized_access() {
         &RecordType::Invoice,
         &String::from_str(&e, "h"),
         &String::from_str(&e, "d"),
+        &usd(&e, 0),
     );
 
     // Stranger cannot see
-    client.get_financial_records(&stranger, &owner);
+    client.get_financial_records(&stranger, &owner, &0, &50);
 }
 
 #[test]
@@ -79,7 +96,7 @@ fn test_revoked_access() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let contract_id = e.register(FinancialRecordContract, ());
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
     let client = FinancialRecordContractClient::new(&e, &contract_id);
 
     let owner = Address::generate(&e);
@@ -90,13 +107,14 @@ fn test_revoked_access() {
         &RecordType::Invoice,
         &String::from_str(&e, "h"),
         &String::from_str(&e, "d"),
+        &usd(&e, 0),
     );
 
-    client.grant_access(&owner, &auditor);
-    client.get_financial_records(&auditor, &owner); // Should be fine
+    client.grant_access(&owner, &auditor, &vec![&e], &0, &u64::MAX, &1_000);
+    client.get_financial_records(&auditor, &owner, &0, &50); // Should be fine
 
     client.revoke_access(&owner, &auditor);
-    client.get_financial_records(&auditor, &owner); // Should panic
+    client.get_financial_records(&auditor, &owner, &0, &50); // Should panic
 }
 
 #[test]
@@ -104,7 +122,7 @@ fn test_filtering() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let contract_id = e.register(FinancialRecordContract, ());
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
     let client = FinancialRecordContractClient::new(&e, &contract_id);
 
     let owner = Address::generate(&e);
@@ -116,6 +134,7 @@ fn test_filtering() {
         &RecordType::Invoice,
         &String::from_str(&e, "h1"),
         &String::from_str(&e, "d1"),
+        &usd(&e, 0),
     );
 
     e.ledger().set_timestamp(200);
@@ -124,6 +143,7 @@ fn test_filtering() {
         &RecordType::TaxDocument,
         &String::from_str(&e, "h2"),
         &String::from_str(&e, "d2"),
+        &usd(&e, 0),
     );
 
     e.ledger().set_timestamp(300);
@@ -132,14 +152,1192 @@ fn test_filtering() {
         &RecordType::Invoice,
         &String::from_str(&e, "h3"),
         &String::from_str(&e, "d3"),
+        &usd(&e, 0),
     );
 
     // Filter by type
-    let invoices = client.get_records_by_type(&owner, &owner, &RecordType::Invoice);
+    let invoices = client
+        .get_records_by_type(&owner, &owner, &RecordType::Invoice, &0, &50)
+        .records;
     assert_eq!(invoices.len(), 2);
 
     // Filter by date range
-    let range_records = client.get_records_by_date_range(&owner, &owner, &150, &250);
+    let range_records = client
+        .get_records_by_date_range(&owner, &owner, &150, &250, &0, &50)
+        .records;
     assert_eq!(range_records.len(), 1);
     assert_eq!(range_records.get(0).unwrap().timestamp, 200);
 }
+
+fn usd(e: &Env, amount: i128) -> Money {
+    Money {
+        amount,
+        currency: String::from_str(e, "USD"),
+    }
+}
+
+fn sample_line_items(e: &Env) -> Vec<InvoiceLineItem> {
+    vec![
+        e,
+        InvoiceLineItem {
+            service_code: String::from_str(e, "99213"),
+            quantity: 2,
+            unit_price: 7_500,
+            tax: 0,
+        },
+        InvoiceLineItem {
+            service_code: String::from_str(e, "A4565"),
+            quantity: 1,
+            unit_price: 2_000,
+            tax: 160,
+        },
+    ]
+}
+
+#[test]
+fn test_create_invoice() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let clinic = Address::generate(&e);
+    let patient = Address::generate(&e);
+
+    e.ledger().set_timestamp(1_000);
+    let invoice_id = client.create_invoice(
+        &clinic,
+        &patient,
+        &sample_line_items(&e),
+        &String::from_str(&e, "USD"),
+        &31_000,
+        &String::from_str(&e, "QmInvoice"),
+        &String::from_str(&e, "Office visit"),
+    );
+
+    let invoice = client.get_invoice(&patient, &invoice_id);
+    assert_eq!(invoice.subtotal, 17_000);
+    assert_eq!(invoice.tax_total, 160);
+    assert_eq!(invoice.total, 17_160);
+    assert_eq!(invoice.status, InvoiceStatus::Issued);
+    assert_eq!(invoice.due_date, 31_000);
+
+    // The issuer keeps an Invoice record pointing at the structured invoice
+    let records = client
+        .get_records_by_type(&clinic, &clinic, &RecordType::Invoice, &0, &50)
+        .records;
+    assert_eq!(records.len(), 1);
+    assert_eq!(invoice.record_index, 0);
+
+    let payer_invoices = client.get_payer_invoices(&patient, &patient);
+    assert_eq!(payer_invoices.len(), 1);
+    assert_eq!(payer_invoices.get(0).unwrap().invoice_id, invoice_id);
+}
+
+#[test]
+fn test_invoice_status_updates() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let clinic = Address::generate(&e);
+    let patient = Address::generate(&e);
+
+    let invoice_id = client.create_invoice(
+        &clinic,
+        &patient,
+        &sample_line_items(&e),
+        &String::from_str(&e, "USD"),
+        &31_000,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+    );
+
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::PartiallyPaid);
+    client.update_invoice_status(&patient, &invoice_id, &InvoiceStatus::Disputed);
+    assert_eq!(
+        client.get_invoice(&clinic, &invoice_id).status,
+        InvoiceStatus::Disputed
+    );
+
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Issued);
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Paid);
+    assert_eq!(
+        client.get_invoice(&patient, &invoice_id).status,
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to update invoice")]
+fn test_payer_cannot_void_invoice() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let clinic = Address::generate(&e);
+    let patient = Address::generate(&e);
+
+    let invoice_id = client.create_invoice(
+        &clinic,
+        &patient,
+        &sample_line_items(&e),
+        &String::from_str(&e, "USD"),
+        &31_000,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+    );
+    client.update_invoice_status(&patient, &invoice_id, &InvoiceStatus::Void);
+}
+
+#[test]
+#[should_panic(expected = "Invalid invoice status transition")]
+fn test_paid_invoice_cannot_be_voided() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let clinic = Address::generate(&e);
+    let patient = Address::generate(&e);
+
+    let invoice_id = client.create_invoice(
+        &clinic,
+        &patient,
+        &sample_line_items(&e),
+        &String::from_str(&e, "USD"),
+        &31_000,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+    );
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Paid);
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Void);
+}
+
+fn setup_payments(
+    e: &Env,
+) -> (
+    FinancialRecordContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    setup_payments_as(e, &Address::generate(e))
+}
+
+/// Like `setup_payments`, with `admin` as the contract admin
+fn setup_payments_as<'a>(
+    e: &'a Env,
+    admin: &Address,
+) -> (
+    FinancialRecordContractClient<'a>,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (admin.clone(),));
+    let client = FinancialRecordContractClient::new(e, &contract_id);
+
+    let clinic = Address::generate(e);
+    let patient = Address::generate(e);
+    let token = e
+        .register_stellar_asset_contract_v2(Address::generate(e))
+        .address();
+    StellarAssetClient::new(e, &token).mint(&patient, &50_000);
+
+    let invoice_id = client.create_invoice(
+        &clinic,
+        &patient,
+        &sample_line_items(e),
+        &String::from_str(e, "USD"),
+        &31_000,
+        &String::from_str(e, "h"),
+        &String::from_str(e, "d"),
+    );
+    client.set_invoice_payment_token(&clinic, &invoice_id, &token);
+
+    (client, clinic, patient, token, invoice_id)
+}
+
+#[test]
+fn test_pay_invoice_partially_and_in_full() {
+    let e = Env::default();
+    let (client, clinic, patient, token, invoice_id) = setup_payments(&e);
+    let token_client = token::Client::new(&e, &token);
+
+    client.pay_invoice(&patient, &invoice_id, &10_000);
+    let invoice = client.get_invoice(&patient, &invoice_id);
+    assert_eq!(invoice.amount_paid, 10_000);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+
+    client.pay_invoice(&patient, &invoice_id, &7_160);
+    let invoice = client.get_invoice(&patient, &invoice_id);
+    assert_eq!(invoice.amount_paid, 17_160);
+    assert_eq!(invoice.status, InvoiceStatus::Paid);
+
+    assert_eq!(token_client.balance(&clinic), 17_160);
+    assert_eq!(token_client.balance(&patient), 32_840);
+
+    let payments = client.get_invoice_payments(&clinic, &invoice_id);
+    assert_eq!(payments.len(), 2);
+    assert!(!payments.get(0).unwrap().is_refund);
+
+    // Both parties get a linked receipt for each payment
+    for party in [&patient, &clinic] {
+        let receipts = client
+            .get_records_by_type(party, party, &RecordType::Receipt, &0, &50)
+            .records;
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts.get(0).unwrap().invoice_id, Some(invoice_id));
+    }
+}
+
+#[test]
+fn test_refund_invoice() {
+    let e = Env::default();
+    let (client, clinic, patient, token, invoice_id) = setup_payments(&e);
+
+    client.pay_invoice(&patient, &invoice_id, &17_160);
+    client.refund_invoice(&clinic, &invoice_id, &2_000);
+
+    let invoice = client.get_invoice(&patient, &invoice_id);
+    assert_eq!(invoice.amount_paid, 15_160);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+    assert_eq!(token::Client::new(&e, &token).balance(&patient), 34_840);
+
+    let payments = client.get_invoice_payments(&patient, &invoice_id);
+    let refund = payments.get(1).unwrap();
+    assert!(refund.is_refund);
+    assert_eq!(refund.from, clinic);
+    assert_eq!(refund.to, patient);
+
+    client.refund_invoice(&clinic, &invoice_id, &15_160);
+    assert_eq!(
+        client.get_invoice(&patient, &invoice_id).status,
+        InvoiceStatus::Issued
+    );
+}
+
+#[test]
+fn test_resolving_dispute_keeps_on_chain_payments() {
+    let e = Env::default();
+    let (client, clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    client.pay_invoice(&patient, &invoice_id, &10_000);
+    client.update_invoice_status(&patient, &invoice_id, &InvoiceStatus::Disputed);
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Issued);
+
+    let invoice = client.get_invoice(&patient, &invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+    assert_eq!(invoice.amount_paid, 10_000);
+
+    assert!(client
+        .try_update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Paid)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Refund payments before voiding")]
+fn test_partially_paid_invoice_cannot_be_voided() {
+    let e = Env::default();
+    let (client, clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    client.pay_invoice(&patient, &invoice_id, &10_000);
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Void);
+}
+
+#[test]
+#[should_panic(expected = "Payment exceeds invoice balance")]
+fn test_overpayment_rejected() {
+    let e = Env::default();
+    let (client, _clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    client.pay_invoice(&patient, &invoice_id, &10_000);
+    client.pay_invoice(&patient, &invoice_id, &10_000);
+}
+
+#[test]
+#[should_panic(expected = "Refund exceeds amount paid")]
+fn test_refund_exceeds_amount_paid() {
+    let e = Env::default();
+    let (client, clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    client.pay_invoice(&patient, &invoice_id, &1_000);
+    client.refund_invoice(&clinic, &invoice_id, &2_000);
+}
+
+fn setup_scoped_records(e: &Env) -> (FinancialRecordContractClient<'_>, Address) {
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(e),));
+    let client = FinancialRecordContractClient::new(e, &contract_id);
+    let owner = Address::generate(e);
+
+    for (timestamp, record_type) in [
+        (100, RecordType::TaxDocument),
+        (200, RecordType::Invoice),
+        (300, RecordType::TaxDocument),
+        (400, RecordType::BankStatement),
+    ] {
+        e.ledger().set_timestamp(timestamp);
+        client.add_financial_record(
+            &owner,
+            &record_type,
+            &String::from_str(e, "h"),
+            &String::from_str(e, "d"),
+            &usd(e, 0),
+        );
+    }
+
+    (client, owner)
+}
+
+#[test]
+fn test_grant_scoped_by_type_and_date() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+    let accountant = Address::generate(&e);
+
+    client.grant_access(
+        &owner,
+        &accountant,
+        &vec![&e, RecordType::TaxDocument, RecordType::Invoice],
+        &150,
+        &350,
+        &10_000,
+    );
+
+    let records = client
+        .get_financial_records(&accountant, &owner, &0, &50)
+        .records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get(0).unwrap().timestamp, 200);
+    assert_eq!(records.get(1).unwrap().timestamp, 300);
+
+    let tax = client
+        .get_records_by_type(&accountant, &owner, &RecordType::TaxDocument, &0, &50)
+        .records;
+    assert_eq!(tax.len(), 1);
+
+    let statements = client
+        .get_records_by_date_range(&accountant, &owner, &0, &1_000, &0, &50)
+        .records;
+    assert_eq!(statements.len(), 2);
+
+    // The owner still sees everything
+    assert_eq!(
+        client
+            .get_financial_records(&owner, &owner, &0, &50)
+            .records
+            .len(),
+        4
+    );
+}
+
+#[test]
+#[should_panic(expected = "Access denied")]
+fn test_expired_grant() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+    let accountant = Address::generate(&e);
+
+    client.grant_access(&owner, &accountant, &vec![&e], &0, &u64::MAX, &1_000);
+    client.get_financial_records(&accountant, &owner, &0, &50);
+
+    e.ledger().set_timestamp(1_000);
+    client.get_financial_records(&accountant, &owner, &0, &50);
+}
+
+#[test]
+fn test_get_active_grants() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+    let accountant = Address::generate(&e);
+    let auditor = Address::generate(&e);
+    let lender = Address::generate(&e);
+
+    client.grant_access(&owner, &accountant, &vec![&e], &0, &u64::MAX, &5_000);
+    client.grant_access(&owner, &auditor, &vec![&e], &0, &u64::MAX, &1_000);
+    client.grant_access(&owner, &lender, &vec![&e], &0, &u64::MAX, &5_000);
+    client.revoke_access(&owner, &lender);
+
+    e.ledger().set_timestamp(2_000);
+    let grants = client.get_active_grants(&owner);
+    assert_eq!(grants.len(), 1);
+    assert_eq!(grants.get(0).unwrap().grantee, accountant);
+}
+
+#[test]
+#[should_panic]
+fn test_caller_must_authenticate() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+
+    // Without mocked auths, passing the owner's address is no longer enough
+    e.set_auths(&[]);
+    client.get_financial_records(&owner, &owner, &0, &50);
+}
+
+const DAY: u64 = 86_400;
+const JAN_1_2025: u64 = 1_735_689_600;
+
+fn setup_monthly_records(e: &Env) -> (FinancialRecordContractClient<'_>, Address) {
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(e),));
+    let client = FinancialRecordContractClient::new(e, &contract_id);
+    let owner = Address::generate(e);
+
+    // One receipt every 10 days through 2025, with a bank statement each 30 days
+    for i in 0..36u64 {
+        e.ledger().set_timestamp(JAN_1_2025 + i * 10 * DAY);
+        let record_type = if i % 3 == 2 {
+            RecordType::BankStatement
+        } else {
+            RecordType::Receipt
+        };
+        client.add_financial_record(
+            &owner,
+            &record_type,
+            &String::from_str(e, "h"),
+            &String::from_str(e, "d"),
+            &usd(e, 0),
+        );
+    }
+
+    (client, owner)
+}
+
+#[test]
+fn test_month_of() {
+    assert_eq!(FinancialRecordContract::month_of(0), 197001);
+    assert_eq!(FinancialRecordContract::month_of(JAN_1_2025 - 1), 202412);
+    assert_eq!(FinancialRecordContract::month_of(JAN_1_2025), 202501);
+    // 2024-02-29 and 2024-03-01
+    assert_eq!(FinancialRecordContract::month_of(1_709_164_800), 202402);
+    assert_eq!(FinancialRecordContract::month_of(1_709_251_200), 202403);
+    assert_eq!(FinancialRecordContract::next_month(202412), 202501);
+    assert_eq!(FinancialRecordContract::month_start(202501), JAN_1_2025);
+    assert_eq!(FinancialRecordContract::month_start(202403), 1_709_251_200);
+    assert_eq!(FinancialRecordContract::month_start(197001), 0);
+}
+
+#[test]
+fn test_paginated_records() {
+    let e = Env::default();
+    let (client, owner) = setup_monthly_records(&e);
+
+    let first = client.get_financial_records(&owner, &owner, &0, &20);
+    assert_eq!(first.records.len(), 20);
+    assert_eq!(first.next_cursor, Some(20));
+
+    let second = client.get_financial_records(&owner, &owner, &20, &20);
+    assert_eq!(second.records.len(), 16);
+    assert_eq!(second.next_cursor, None);
+
+    let mut cursor = 0;
+    let mut statements = 0;
+    loop {
+        let page =
+            client.get_records_by_type(&owner, &owner, &RecordType::BankStatement, &cursor, &5);
+        statements += page.records.len();
+        match page.next_cursor {
+            Some(next) => cursor = next,
+            None => break,
+        }
+    }
+    assert_eq!(statements, 12);
+}
+
+#[test]
+fn test_date_range_uses_month_index() {
+    let e = Env::default();
+    let (client, owner) = setup_monthly_records(&e);
+
+    // March 2025: days 59..90 of the year hold records 6, 7 and 8
+    let march_start = JAN_1_2025 + 59 * DAY;
+    let march_end = JAN_1_2025 + 90 * DAY - 1;
+    let page = client.get_records_by_date_range(&owner, &owner, &march_start, &march_end, &0, &50);
+    assert_eq!(page.records.len(), 3);
+    assert_eq!(
+        page.records.get(0).unwrap().timestamp,
+        JAN_1_2025 + 60 * DAY
+    );
+    assert_eq!(page.next_cursor, None);
+
+    // The whole year, two records at a time
+    let page = client.get_records_by_date_range(&owner, &owner, &0, &u64::MAX, &0, &2);
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.next_cursor, Some(2));
+    let page = client.get_records_by_date_range(&owner, &owner, &0, &u64::MAX, &34, &2);
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_date_range_limits_months_scanned() {
+    let e = Env::default();
+    let (client, owner) = setup_monthly_records(&e);
+
+    // A record three years on, after a run of empty months
+    e.ledger().set_timestamp(JAN_1_2025 + 3 * 365 * DAY);
+    client.add_financial_record(
+        &owner,
+        &RecordType::Receipt,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+        &usd(&e, 0),
+    );
+
+    let start = JAN_1_2025 + 360 * DAY;
+    let page = client.get_records_by_date_range(&owner, &owner, &start, &u64::MAX, &0, &50);
+    assert_eq!(page.records.len(), 0);
+    assert_eq!(page.next_cursor, Some(36));
+
+    let page = client.get_records_by_date_range(&owner, &owner, &start, &u64::MAX, &36, &50);
+    assert_eq!(page.records.len(), 1);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_records_by_type_and_range() {
+    let e = Env::default();
+    let (client, owner) = setup_monthly_records(&e);
+
+    let start = JAN_1_2025 + 100 * DAY;
+    let end = JAN_1_2025 + 200 * DAY;
+    let page = client.get_records_by_type_and_range(
+        &owner,
+        &owner,
+        &RecordType::BankStatement,
+        &start,
+        &end,
+        &0,
+        &50,
+    );
+    // Statements fall on days 20, 50, 80, ... so days 110 through 200
+    assert_eq!(page.records.len(), 4);
+    assert_eq!(
+        page.records.get(0).unwrap().timestamp,
+        JAN_1_2025 + 110 * DAY
+    );
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_amend_and_void_records() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+
+    let amendment = client.amend_financial_record(
+        &owner,
+        &0,
+        &String::from_str(&e, "h-corrected"),
+        &String::from_str(&e, "Corrected 1099"),
+        &usd(&e, 0),
+    );
+    assert_eq!(amendment, 4);
+    let entry = client.get_record(&owner, &owner, &amendment);
+    assert_eq!(entry.entry_kind, EntryKind::Amendment);
+    assert_eq!(entry.amends, Some(0));
+    assert_eq!(entry.record_type, RecordType::TaxDocument);
+
+    let void = client.void_financial_record(&owner, &3, &String::from_str(&e, "Duplicate"));
+    assert_eq!(
+        client.get_record(&owner, &owner, &void).entry_kind,
+        EntryKind::Void
+    );
+
+    // Originals are kept alongside their status
+    assert_eq!(
+        client.get_record(&owner, &owner, &0).ipfs_hash,
+        String::from_str(&e, "h")
+    );
+    assert_eq!(
+        client.get_record_status(&owner, &owner, &0),
+        RecordStatus::Amended
+    );
+    assert_eq!(
+        client.get_record_status(&owner, &owner, &3),
+        RecordStatus::Voided
+    );
+    assert_eq!(
+        client.get_record_status(&owner, &owner, &1),
+        RecordStatus::Active
+    );
+    assert_eq!(
+        client.get_record_status(&owner, &owner, &amendment),
+        RecordStatus::Active
+    );
+
+    let genesis = BytesN::from_array(&e, &[0; 32]);
+    assert!(client.verify_chain(&owner, &0, &50, &genesis).valid);
+}
+
+#[test]
+#[should_panic(expected = "Record already amended or voided")]
+fn test_cannot_amend_superseded_record() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+
+    client.void_financial_record(&owner, &1, &String::from_str(&e, "Entered in error"));
+    client.amend_financial_record(
+        &owner,
+        &1,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+        &usd(&e, 0),
+    );
+}
+
+#[test]
+fn test_verify_chain_detects_tampering() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let genesis = BytesN::from_array(&e, &[0; 32]);
+
+    assert!(client.verify_chain(&owner, &0, &50, &genesis).valid);
+    for _ in 0..3 {
+        client.add_financial_record(
+            &owner,
+            &RecordType::TaxDocument,
+            &String::from_str(&e, "h"),
+            &String::from_str(&e, "d"),
+            &usd(&e, 0),
+        );
+    }
+    let second = client.get_record(&owner, &owner, &1);
+    assert_ne!(second.prev_hash, genesis);
+    assert!(client.verify_chain(&owner, &0, &50, &genesis).valid);
+
+    // Checking in pages carries the running hash from one page to the next
+    let first = client.verify_chain(&owner, &0, &2, &genesis);
+    assert!(first.valid);
+    assert_eq!(first.next_cursor, Some(2));
+    let last = client.verify_chain(&owner, &2, &2, &first.hash);
+    assert!(last.valid);
+    assert_eq!(last.next_cursor, None);
+    assert!(!client.verify_chain(&owner, &2, &2, &genesis).valid);
+
+    let mut forged = second.clone();
+    forged.description = String::from_str(&e, "forged");
+    e.as_contract(&contract_id, || {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Record(owner.clone(), 1), &forged);
+    });
+    assert!(!client.verify_chain(&owner, &0, &50, &genesis).valid);
+    let first = client.verify_chain(&owner, &0, &2, &genesis);
+    assert!(first.valid);
+    assert!(!client.verify_chain(&owner, &2, &2, &first.hash).valid);
+}
+
+#[test]
+fn test_tax_year_summary_from_records() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, (Address::generate(&e),));
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+
+    e.ledger().set_timestamp(JAN_1_2025 - DAY);
+    client.add_financial_record(
+        &owner,
+        &RecordType::Receipt,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "December pharmacy"),
+        &usd(&e, 4_000),
+    );
+
+    e.ledger().set_timestamp(JAN_1_2025 + 10 * DAY);
+    for (record_type, amount) in [
+        (RecordType::Receipt, 12_000),
+        (RecordType::Receipt, 3_000),
+        (RecordType::Reimbursement, 9_000),
+    ] {
+        client.add_financial_record(
+            &owner,
+            &record_type,
+            &String::from_str(&e, "h"),
+            &String::from_str(&e, "d"),
+            &usd(&e, amount),
+        );
+    }
+
+    let summary = client.get_tax_year_summary(&owner, &owner, &2025, &String::from_str(&e, "USD"));
+    assert_eq!(summary.medical_expenses_paid, 15_000);
+    assert_eq!(summary.reimbursements_received, 9_000);
+    assert_eq!(summary.record_counts.get(RecordType::Receipt), Some(2));
+    assert_eq!(summary.record_totals.get(RecordType::Receipt), Some(15_000));
+    assert_eq!(
+        client
+            .get_tax_year_summary(&owner, &owner, &2024, &String::from_str(&e, "USD"))
+            .medical_expenses_paid,
+        4_000
+    );
+
+    // Correcting the December receipt in January still adjusts 2024
+    client.amend_financial_record(
+        &owner,
+        &0,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "December pharmacy, corrected"),
+        &usd(&e, 4_500),
+    );
+    client.void_financial_record(&owner, &2, &String::from_str(&e, "Duplicate"));
+
+    let summary_2024 =
+        client.get_tax_year_summary(&owner, &owner, &2024, &String::from_str(&e, "USD"));
+    assert_eq!(summary_2024.medical_expenses_paid, 4_500);
+    assert_eq!(summary_2024.record_counts.get(RecordType::Receipt), Some(1));
+    let summary = client.get_tax_year_summary(&owner, &owner, &2025, &String::from_str(&e, "USD"));
+    assert_eq!(summary.medical_expenses_paid, 12_000);
+    assert_eq!(summary.record_counts.get(RecordType::Receipt), Some(1));
+}
+
+#[test]
+fn test_tax_year_summary_from_invoices() {
+    let e = Env::default();
+    e.ledger().set_timestamp(JAN_1_2025);
+    let (client, clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    let summary =
+        client.get_tax_year_summary(&patient, &patient, &2025, &String::from_str(&e, "USD"));
+    assert_eq!(summary.outstanding_invoices, 17_160);
+
+    client.pay_invoice(&patient, &invoice_id, &10_000);
+    client.refund_invoice(&clinic, &invoice_id, &1_000);
+
+    let patient_summary =
+        client.get_tax_year_summary(&patient, &patient, &2025, &String::from_str(&e, "USD"));
+    assert_eq!(patient_summary.medical_expenses_paid, 9_000);
+    assert_eq!(patient_summary.outstanding_invoices, 8_160);
+    assert_eq!(
+        patient_summary.record_totals.get(RecordType::Receipt),
+        Some(9_000)
+    );
+
+    let clinic_summary =
+        client.get_tax_year_summary(&clinic, &clinic, &2025, &String::from_str(&e, "USD"));
+    assert_eq!(clinic_summary.payments_received, 9_000);
+    assert_eq!(clinic_summary.medical_expenses_paid, 0);
+    assert_eq!(clinic_summary.outstanding_invoices, 8_160);
+
+    client.refund_invoice(&clinic, &invoice_id, &9_000);
+    client.update_invoice_status(&clinic, &invoice_id, &InvoiceStatus::Void);
+    assert_eq!(
+        client
+            .get_tax_year_summary(&patient, &patient, &2025, &String::from_str(&e, "USD"))
+            .outstanding_invoices,
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "Access denied")]
+fn test_type_scoped_grant_cannot_read_summary() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+    let accountant = Address::generate(&e);
+
+    client.grant_access(
+        &owner,
+        &accountant,
+        &vec![&e, RecordType::TaxDocument],
+        &0,
+        &u64::MAX,
+        &10_000,
+    );
+    client.get_tax_year_summary(&accountant, &owner, &1970, &String::from_str(&e, "USD"));
+}
+
+#[test]
+#[should_panic(expected = "Access denied")]
+fn test_date_scoped_grant_cannot_read_other_years() {
+    let e = Env::default();
+    let (client, owner) = setup_scoped_records(&e);
+    let accountant = Address::generate(&e);
+
+    // The whole of 2025 is visible, so 2025 can be summarised but 2024 cannot
+    client.grant_access(
+        &owner,
+        &accountant,
+        &vec![&e],
+        &JAN_1_2025,
+        &(JAN_1_2025 + 365 * DAY - 1),
+        &10_000,
+    );
+    client.get_tax_year_summary(&accountant, &owner, &2025, &String::from_str(&e, "USD"));
+    client.get_tax_year_summary(&accountant, &owner, &2024, &String::from_str(&e, "USD"));
+}
+
+#[test]
+fn test_payment_plan_installments() {
+    let e = Env::default();
+    e.ledger().set_timestamp(JAN_1_2025);
+    let (client, _clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    let start = JAN_1_2025 + 30 * DAY;
+    let plan_id = client.create_payment_plan(&invoice_id, &6_000, &(30 * DAY), &start);
+
+    let plan = client.get_payment_plan(&patient, &plan_id);
+    assert_eq!(plan.principal, 17_160);
+    assert_eq!(plan.installment_count, 3);
+    assert_eq!(plan.next_due_date, start);
+    assert_eq!(plan.status, PlanStatus::Active);
+
+    client.pay_invoice(&patient, &invoice_id, &6_000);
+    let plan = client.get_payment_plan(&patient, &plan_id);
+    assert_eq!(plan.installments_paid, 1);
+    assert_eq!(plan.next_due_date, start + 30 * DAY);
+
+    e.ledger().set_timestamp(start + 31 * DAY);
+    client.pay_invoice(&patient, &invoice_id, &6_000);
+    client.pay_invoice(&patient, &invoice_id, &5_160);
+    let plan = client.get_payment_plan(&patient, &plan_id);
+    assert_eq!(plan.status, PlanStatus::Completed);
+    assert_eq!(plan.installments_paid, 3);
+    assert_eq!(plan.next_due_date, 0);
+}
+
+#[test]
+fn test_mark_overdue_flags_delinquent_plans() {
+    let e = Env::default();
+    e.ledger().set_timestamp(JAN_1_2025);
+    let (client, _clinic, patient, _token, invoice_id) = setup_payments(&e);
+
+    let start = JAN_1_2025 + 30 * DAY;
+    let plan_id = client.create_payment_plan(&invoice_id, &6_000, &(30 * DAY), &start);
+
+    // Nothing is due yet
+    assert_eq!(client.mark_overdue(&0, &50), None);
+    assert_eq!(
+        client.get_payment_plan(&patient, &plan_id).status,
+        PlanStatus::Active
+    );
+
+    // Two due dates pass unpaid
+    e.ledger().set_timestamp(start + 30 * DAY + 1);
+    client.mark_overdue(&0, &50);
+    assert_eq!(e.events().all().len(), 1);
+    let plan = client.get_payment_plan(&patient, &plan_id);
+    assert_eq!(plan.status, PlanStatus::Delinquent);
+    assert_eq!(plan.missed_installments, 2);
+
+    // Sweeping again without new misses emits nothing
+    client.mark_overdue(&0, &50);
+    assert_eq!(e.events().all().len(), 0);
+
+    // Catching up restores the plan
+    client.pay_invoice(&patient, &invoice_id, &12_000);
+    let plan = client.get_payment_plan(&patient, &plan_id);
+    assert_eq!(plan.status, PlanStatus::Active);
+    assert_eq!(plan.missed_installments, 0);
+}
+
+#[test]
+#[should_panic(expected = "Invoice already has a payment plan")]
+fn test_one_payment_plan_per_invoice() {
+    let e = Env::default();
+    let (client, _clinic, _patient, _token, invoice_id) = setup_payments(&e);
+
+    client.create_payment_plan(&invoice_id, &6_000, &(30 * DAY), &DAY);
+    client.create_payment_plan(&invoice_id, &3_000, &(30 * DAY), &DAY);
+}
+
+#[test]
+#[should_panic(expected = "Invalid installment amount")]
+fn test_payment_plan_installment_count_must_fit() {
+    let e = Env::default();
+    let (client, clinic, patient, _token, _invoice_id) = setup_payments(&e);
+
+    let invoice_id = client.create_invoice(
+        &clinic,
+        &patient,
+        &vec![
+            &e,
+            InvoiceLineItem {
+                service_code: String::from_str(&e, "99213"),
+                quantity: 1,
+                unit_price: 1 << 40,
+                tax: 0,
+            },
+        ],
+        &String::from_str(&e, "USD"),
+        &31_000,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+    );
+    client.create_payment_plan(&invoice_id, &1, &(30 * DAY), &DAY);
+}
+
+fn setup_hsa(
+    e: &Env,
+) -> (
+    FinancialRecordContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    u64,
+) {
+    let admin = Address::generate(e);
+    let (client, clinic, patient, token, invoice_id) = setup_payments_as(e, &admin);
+
+    client.register_provider(&admin, &clinic);
+    for code in ["99213", "A4565"] {
+        client.add_eligible_service(&admin, &String::from_str(e, code));
+    }
+
+    client.open_vault(&patient, &token);
+    client.deposit_to_vault(&patient, &30_000);
+
+    (client, admin, clinic, patient, invoice_id)
+}
+
+#[test]
+fn test_release_from_vault() {
+    let e = Env::default();
+    let (client, _admin, clinic, patient, invoice_id) = setup_hsa(&e);
+    let token = client.get_vault(&patient).token;
+    let token_client = token::Client::new(&e, &token);
+    assert_eq!(token_client.balance(&patient), 20_000);
+
+    client.set_provider_cap(&patient, &clinic, &Some(15_000));
+    client.release_from_vault(&patient, &invoice_id, &10_000);
+
+    let vault = client.get_vault(&patient);
+    assert_eq!(vault.balance, 20_000);
+    assert_eq!(vault.total_released, 10_000);
+    assert_eq!(token_client.balance(&clinic), 10_000);
+    assert_eq!(client.get_provider_spent(&patient, &clinic), 10_000);
+
+    let invoice = client.get_invoice(&patient, &invoice_id);
+    assert_eq!(invoice.amount_paid, 10_000);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+
+    let receipts = client
+        .get_records_by_type(&patient, &patient, &RecordType::Receipt, &0, &50)
+        .records;
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts.get(0).unwrap().invoice_id, Some(invoice_id));
+}
+
+#[test]
+fn test_refund_of_vault_payment_returns_to_vault() {
+    let e = Env::default();
+    let (client, _admin, clinic, patient, invoice_id) = setup_hsa(&e);
+    let token = client.get_vault(&patient).token;
+    let token_client = token::Client::new(&e, &token);
+
+    client.set_provider_cap(&patient, &clinic, &Some(15_000));
+    client.release_from_vault(&patient, &invoice_id, &10_000);
+    client.refund_invoice(&clinic, &invoice_id, &4_000);
+
+    let vault = client.get_vault(&patient);
+    assert_eq!(vault.balance, 24_000);
+    assert_eq!(vault.total_released, 6_000);
+    assert_eq!(token_client.balance(&patient), 20_000);
+    assert_eq!(token_client.balance(&clinic), 6_000);
+    assert_eq!(client.get_provider_spent(&patient, &clinic), 6_000);
+
+    let refund = client
+        .get_invoice_payments(&patient, &invoice_id)
+        .get(1)
+        .unwrap();
+    assert!(refund.is_refund);
+    assert!(refund.via_vault);
+
+    // The refunded amount is available under the cap again.
+    client.release_from_vault(&patient, &invoice_id, &9_000);
+    assert_eq!(client.get_provider_spent(&patient, &clinic), 15_000);
+}
+
+#[test]
+#[should_panic(expected = "Provider spending cap exceeded")]
+fn test_vault_provider_cap() {
+    let e = Env::default();
+    let (client, _admin, clinic, patient, invoice_id) = setup_hsa(&e);
+
+    client.set_provider_cap(&patient, &clinic, &Some(15_000));
+    client.release_from_vault(&patient, &invoice_id, &10_000);
+    client.release_from_vault(&patient, &invoice_id, &7_160);
+}
+
+#[test]
+#[should_panic(expected = "Invoice contains ineligible services")]
+fn test_vault_rejects_ineligible_services() {
+    let e = Env::default();
+    let (client, admin, _clinic, patient, invoice_id) = setup_hsa(&e);
+
+    client.remove_eligible_service(&admin, &String::from_str(&e, "A4565"));
+    client.release_from_vault(&patient, &invoice_id, &10_000);
+}
+
+#[test]
+#[should_panic(expected = "Provider not registered")]
+fn test_vault_rejects_unregistered_provider() {
+    let e = Env::default();
+    let (client, admin, clinic, patient, invoice_id) = setup_hsa(&e);
+
+    client.remove_provider(&admin, &clinic);
+    client.release_from_vault(&patient, &invoice_id, &10_000);
+}
+
+#[test]
+fn test_exchange_rate_history() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = FinancialRecordContractClient::new(
+        &e,
+        &e.register(FinancialRecordContract, (admin.clone(),)),
+    );
+    let oracle = Address::generate(&e);
+    client.add_rate_oracle(&admin, &oracle);
+
+    let eur_code = String::from_str(&e, "EUR");
+    let usd_code = String::from_str(&e, "USD");
+    e.ledger().set_timestamp(JAN_1_2025 + 30 * DAY);
+    client.set_exchange_rate(
+        &oracle,
+        &eur_code,
+        &usd_code,
+        &12_000_000,
+        &(JAN_1_2025 + 10 * DAY),
+    );
+    client.set_exchange_rate(&admin, &eur_code, &usd_code, &10_000_000, &JAN_1_2025);
+
+    assert_eq!(
+        client.get_exchange_rate(&eur_code, &usd_code, &JAN_1_2025),
+        10_000_000
+    );
+    assert_eq!(
+        client.get_exchange_rate(&eur_code, &usd_code, &(JAN_1_2025 + 5 * DAY)),
+        10_000_000
+    );
+    assert_eq!(
+        client.get_exchange_rate(&eur_code, &usd_code, &(JAN_1_2025 + 20 * DAY)),
+        12_000_000
+    );
+    assert_eq!(
+        client.get_exchange_rate(&usd_code, &usd_code, &0),
+        10_000_000
+    );
+
+    // A correction for the same timestamp replaces the earlier rate,
+    // and the reverse pair is derived by inversion
+    client.set_exchange_rate(&oracle, &eur_code, &usd_code, &8_000_000, &JAN_1_2025);
+    assert_eq!(
+        client.get_exchange_rate(&usd_code, &eur_code, &(JAN_1_2025 + 5 * DAY)),
+        12_500_000
+    );
+
+    assert!(client
+        .try_get_exchange_rate(&eur_code, &usd_code, &(JAN_1_2025 - 1))
+        .is_err());
+
+    client.remove_rate_oracle(&admin, &oracle);
+    assert!(client
+        .try_set_exchange_rate(&oracle, &eur_code, &usd_code, &9_000_000, &JAN_1_2025)
+        .is_err());
+}
+
+#[test]
+fn test_reporting_summary_converts_at_record_time() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = FinancialRecordContractClient::new(
+        &e,
+        &e.register(FinancialRecordContract, (admin.clone(),)),
+    );
+    let owner = Address::generate(&e);
+
+    let eur_code = String::from_str(&e, "EUR");
+    let usd_code = String::from_str(&e, "USD");
+    e.ledger().set_timestamp(JAN_1_2025 + 40 * DAY);
+    client.set_exchange_rate(&admin, &eur_code, &usd_code, &11_000_000, &JAN_1_2025);
+    client.set_exchange_rate(
+        &admin,
+        &eur_code,
+        &usd_code,
+        &12_000_000,
+        &(JAN_1_2025 + 31 * DAY),
+    );
+
+    let receipt = |ts: u64, amount: Money| {
+        e.ledger().set_timestamp(ts);
+        client.add_financial_record(
+            &owner,
+            &RecordType::Receipt,
+            &String::from_str(&e, "QmReceipt"),
+            &String::from_str(&e, "Pharmacy"),
+            &amount,
+        );
+    };
+    receipt(JAN_1_2025 + DAY, usd(&e, 5_000));
+    receipt(
+        JAN_1_2025 + 2 * DAY,
+        Money {
+            amount: 10_000,
+            currency: eur_code.clone(),
+        },
+    );
+    receipt(
+        JAN_1_2025 + 35 * DAY,
+        Money {
+            amount: 10_000,
+            currency: eur_code.clone(),
+        },
+    );
+
+    // Summaries stay in the record's own currency
+    let eur_summary = client.get_tax_year_summary(&owner, &owner, &2025, &eur_code);
+    assert_eq!(eur_summary.medical_expenses_paid, 20_000);
+
+    let first = client.get_reporting_summary(&owner, &owner, &2025, &usd_code, &0, &2);
+    assert_eq!(first.medical_expenses_paid, 5_000 + 11_000);
+    assert_eq!(first.next_cursor, Some(2));
+    let rest = client.get_reporting_summary(&owner, &owner, &2025, &usd_code, &2, &2);
+    assert_eq!(rest.medical_expenses_paid, 12_000);
+    assert_eq!(rest.record_totals.get(RecordType::Receipt), Some(12_000));
+    assert_eq!(rest.next_cursor, None);
+}
+
+#[test]
+#[should_panic(expected = "Invalid currency code")]
+fn test_invalid_currency_code() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let client = FinancialRecordContractClient::new(
+        &e,
+        &e.register(FinancialRecordContract, (Address::generate(&e),)),
+    );
+    let owner = Address::generate(&e);
+
+    client.add_financial_record(
+        &owner,
+        &RecordType::Receipt,
+        &String::from_str(&e, "QmReceipt"),
+        &String::from_str(&e, "Pharmacy"),
+        &Money {
+            amount: 100,
+            currency: String::from_str(&e, "usd"),
+        },
+    );
+}
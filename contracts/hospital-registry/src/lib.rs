@@ -1,6 +1,9 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    String, Vec,
+};
 
 /// --------------------
 /// Hospital Structures
@@ -63,8 +66,10 @@ pub struct AlertSetting {
 pub struct InsuranceProviderConfig {
     pub provider_name: String,
     pub plan_codes: Vec<String>,
-    pub billing_contact: String,
+    pub billing_contact: Option<String>, // Free-text contact, only when not linked by id
     pub metadata: String,
+    pub insurer_wallet: Option<Address>, // Insurer's wallet in the insurer registry
+    pub billing_contact_id: Option<u32>, // Insurer registry contact id, set with `insurer_wallet`
 }
 
 #[contracttype]
@@ -97,6 +102,38 @@ pub struct HospitalConfig {
     pub emergency_protocols: Vec<EmergencyProtocol>,
 }
 
+/// --------------------
+/// Accreditation Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub id: u32,
+    pub issuer: Address,
+    pub credential_type: String, // e.g. "JCI", "STATE_LICENSE", "TRAUMA_LEVEL_II"
+    pub document_hash: BytesN<32>,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+/// A page of filtered directory results
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalPage {
+    pub hospitals: Vec<Address>,
+    pub next_cursor: Option<u32>, // Index position to resume from, None once exhausted
+}
+
+/// --------------------
+/// Insurer Registry
+/// --------------------
+/// Insurance providers linked by wallet are checked against the insurer registry
+#[contractclient(name = "InsurerRegistryClient")]
+pub trait InsurerRegistryInterface {
+    fn has_contact(env: Env, wallet: Address, contact_id: u32) -> bool;
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
@@ -104,8 +141,21 @@ pub struct HospitalConfig {
 pub enum DataKey {
     Hospital(Address),
     HospitalConfig(Address),
+    Admin,
+    AccreditingBody(Address),  // Accrediting body -> bool
+    Attestations(Address),     // Hospital -> Vec<Attestation>
+    HospitalCount,             // Number of registered hospitals
+    HospitalIndex(u32),        // Registration index -> Hospital wallet
+    HospitalRegion(Address),   // Hospital -> region code
+    HospitalServices(Address), // Hospital -> Vec<String> of indexed services
+    RegionHospitals(String),   // Region code -> Vec<Address>
+    ServiceHospitals(String),  // Department/service name -> Vec<Address>
+    InsurerRegistry,           // Address of the insurer registry contract
 }
 
+/// Upper bound on the number of entries returned by a single directory page
+const MAX_PAGE_SIZE: u32 = 50;
+
 #[contract]
 pub struct HospitalRegistry;
 
@@ -135,6 +185,89 @@ impl HospitalRegistry {
         }
     }
 
+    fn add_to_index(env: &Env, key: &DataKey, wallet: &Address) {
+        let mut wallets: Vec<Address> =
+            env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        if !wallets.contains(wallet) {
+            wallets.push_back(wallet.clone());
+            env.storage().persistent().set(key, &wallets);
+        }
+    }
+
+    fn remove_from_index(env: &Env, key: &DataKey, wallet: &Address) {
+        let mut wallets: Vec<Address> =
+            env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        if let Some(i) = wallets.first_index_of(wallet) {
+            wallets.remove(i);
+            env.storage().persistent().set(key, &wallets);
+        }
+    }
+
+    /// Check each insurance provider has exactly one billing contact: free text,
+    /// or an insurer wallet and contact id that exist in the insurer registry
+    fn check_insurance_providers(env: &Env, providers: &Vec<InsuranceProviderConfig>) {
+        for provider in providers.iter() {
+            match (provider.insurer_wallet, provider.billing_contact_id) {
+                (Some(insurer_wallet), Some(contact_id)) => {
+                    if provider.billing_contact.is_some() {
+                        panic!("Billing contact is linked by id");
+                    }
+                    let registry: Address = env
+                        .storage()
+                        .instance()
+                        .get(&DataKey::InsurerRegistry)
+                        .expect("Insurer registry not configured");
+                    if !InsurerRegistryClient::new(env, &registry)
+                        .has_contact(&insurer_wallet, &contact_id)
+                    {
+                        panic!("Billing contact not found");
+                    }
+                }
+                (None, None) => {
+                    if provider.billing_contact.is_none() {
+                        panic!("Billing contact required");
+                    }
+                }
+                _ => panic!("Insurer wallet and billing contact id must be set together"),
+            }
+        }
+    }
+
+    /// Re-index the services a hospital offers from its configured departments
+    fn index_services(env: &Env, wallet: &Address, departments: &Vec<Department>) {
+        let services_key = DataKey::HospitalServices(wallet.clone());
+        let previous: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&services_key)
+            .unwrap_or(Vec::new(env));
+        for service in previous.iter() {
+            Self::remove_from_index(env, &DataKey::ServiceHospitals(service), wallet);
+        }
+
+        let mut services: Vec<String> = Vec::new(env);
+        for department in departments.iter() {
+            if !services.contains(&department.name) {
+                Self::add_to_index(
+                    env,
+                    &DataKey::ServiceHospitals(department.name.clone()),
+                    wallet,
+                );
+                services.push_back(department.name);
+            }
+        }
+        env.storage().persistent().set(&services_key, &services);
+    }
+
+    fn paginate(env: &Env, wallets: Vec<Address>, offset: u32, limit: u32) -> Vec<Address> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        if offset >= wallets.len() {
+            return Vec::new(env);
+        }
+        let end = offset.saturating_add(limit).min(wallets.len());
+        wallets.slice(offset..end)
+    }
+
     /// Register a new hospital with basic information
     ///
     /// # Arguments
@@ -168,6 +301,18 @@ impl HospitalRegistry {
         let config = Self::default_config(&env);
         env.storage().persistent().set(&config_key, &config);
 
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::HospitalCount)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::HospitalIndex(count), &wallet);
+        env.storage()
+            .persistent()
+            .set(&DataKey::HospitalCount, &(count + 1));
+
         env.events().publish(
             (symbol_short!("reg_hosp"), wallet),
             symbol_short!("success"),
@@ -217,6 +362,9 @@ impl HospitalRegistry {
     pub fn set_hospital_config(env: Env, wallet: Address, config: HospitalConfig) {
         wallet.require_auth();
         Self::assert_hospital_exists(&env, &wallet);
+        Self::check_insurance_providers(&env, &config.insurance_providers);
+
+        Self::index_services(&env, &wallet, &config.departments);
 
         let key = DataKey::HospitalConfig(wallet.clone());
         env.storage().persistent().set(&key, &config);
@@ -245,6 +393,8 @@ impl HospitalRegistry {
             .get(&key)
             .expect("Hospital config not found");
 
+        Self::index_services(&env, &wallet, &departments);
+
         config.departments = departments;
         env.storage().persistent().set(&key, &config);
 
@@ -335,6 +485,7 @@ impl HospitalRegistry {
     ) {
         wallet.require_auth();
         Self::assert_hospital_exists(&env, &wallet);
+        Self::check_insurance_providers(&env, &insurance_providers);
 
         let key = DataKey::HospitalConfig(wallet.clone());
         let mut config: HospitalConfig = env
@@ -391,6 +542,371 @@ impl HospitalRegistry {
         env.events()
             .publish((symbol_short!("upd_emg"), wallet), symbol_short!("success"));
     }
+
+    // =====================================================
+    //                 DIRECTORY & SEARCH
+    // =====================================================
+
+    /// Set the region code a hospital is listed under (e.g. "US-NY")
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the hospital
+    /// * `region` - The region code used for directory lookups
+    pub fn set_hospital_region(env: Env, wallet: Address, region: String) {
+        wallet.require_auth();
+        Self::assert_hospital_exists(&env, &wallet);
+
+        let region_key = DataKey::HospitalRegion(wallet.clone());
+        if let Some(previous) = env.storage().persistent().get::<_, String>(&region_key) {
+            Self::remove_from_index(&env, &DataKey::RegionHospitals(previous), &wallet);
+        }
+
+        Self::add_to_index(&env, &DataKey::RegionHospitals(region.clone()), &wallet);
+        env.storage().persistent().set(&region_key, &region);
+
+        env.events().publish(
+            (symbol_short!("upd_regn"), wallet),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Retrieve the region code a hospital is listed under, if any
+    pub fn get_hospital_region(env: Env, wallet: Address) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HospitalRegion(wallet))
+    }
+
+    /// Total number of registered hospitals
+    pub fn get_hospital_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HospitalCount)
+            .unwrap_or(0)
+    }
+
+    /// List registered hospitals in registration order
+    ///
+    /// # Arguments
+    /// * `offset` - Number of hospitals to skip
+    /// * `limit` - Maximum number of hospitals to return (capped at 50)
+    pub fn list_hospitals(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        let count = Self::get_hospital_count(env.clone());
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut wallets = Vec::new(&env);
+        for i in offset..end {
+            if let Some(wallet) = env.storage().persistent().get(&DataKey::HospitalIndex(i)) {
+                wallets.push_back(wallet);
+            }
+        }
+        wallets
+    }
+
+    /// List hospitals in a region
+    ///
+    /// # Arguments
+    /// * `region` - The region code
+    /// * `offset` - Number of hospitals to skip
+    /// * `limit` - Maximum number of hospitals to return (capped at 50)
+    pub fn get_hospitals_by_region(
+        env: Env,
+        region: String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        let wallets: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RegionHospitals(region))
+            .unwrap_or(Vec::new(&env));
+        Self::paginate(&env, wallets, offset, limit)
+    }
+
+    /// List hospitals offering a department or service (matched on department name)
+    ///
+    /// # Arguments
+    /// * `service` - The department or service name, e.g. "Cardiology"
+    /// * `offset` - Number of hospitals to skip
+    /// * `limit` - Maximum number of hospitals to return (capped at 50)
+    pub fn get_hospitals_by_service(
+        env: Env,
+        service: String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        let wallets: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ServiceHospitals(service))
+            .unwrap_or(Vec::new(&env));
+        Self::paginate(&env, wallets, offset, limit)
+    }
+
+    /// List hospitals in a region that offer a given department or service
+    ///
+    /// # Arguments
+    /// * `region` - The region code
+    /// * `service` - The department or service name
+    /// * `cursor` - Index position to start from: 0, or the previous page's `next_cursor`
+    /// * `limit` - Maximum number of region entries to examine (capped at 50),
+    ///   so a page may be short or empty and still carry a cursor
+    pub fn search_hospitals(
+        env: Env,
+        region: String,
+        service: String,
+        cursor: u32,
+        limit: u32,
+    ) -> HospitalPage {
+        let in_region: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RegionHospitals(region))
+            .unwrap_or(Vec::new(&env));
+
+        let mut budget = limit.min(MAX_PAGE_SIZE);
+        let mut page = HospitalPage {
+            hospitals: Vec::new(&env),
+            next_cursor: None,
+        };
+        for i in cursor..in_region.len() {
+            if budget == 0 {
+                page.next_cursor = Some(i);
+                break;
+            }
+            budget -= 1;
+            let wallet = in_region.get_unchecked(i);
+            let services: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::HospitalServices(wallet.clone()))
+                .unwrap_or(Vec::new(&env));
+            if services.contains(&service) {
+                page.hospitals.push_back(wallet);
+            }
+        }
+        page
+    }
+
+    // =====================================================
+    //          ACCREDITATION & LICENSE ATTESTATIONS
+    // =====================================================
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *admin != stored_admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+
+    /// Set the admin who governs accrediting bodies when the contract is deployed,
+    /// so that no later caller can claim the admin role
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address for the contract
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        env.events()
+            .publish((symbol_short!("init"), admin), symbol_short!("success"));
+    }
+
+    /// Set the insurer registry that linked insurance providers are checked against (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `insurer_registry` - The address of the insurer registry contract
+    pub fn set_insurer_registry(env: Env, admin: Address, insurer_registry: Address) {
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::InsurerRegistry, &insurer_registry);
+
+        env.events().publish(
+            (symbol_short!("set_ireg"), insurer_registry),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Add an accrediting body allowed to issue attestations (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `body` - The wallet address of the accrediting body or licensing authority
+    pub fn add_accrediting_body(env: Env, admin: Address, body: Address) {
+        Self::require_admin(&env, &admin);
+
+        let key = DataKey::AccreditingBody(body.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("Accrediting body already registered");
+        }
+        env.storage().persistent().set(&key, &true);
+
+        env.events()
+            .publish((symbol_short!("add_body"), body), symbol_short!("success"));
+    }
+
+    /// Remove an accrediting body (admin only). Attestations it issued stop
+    /// counting as valid.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `body` - The wallet address of the accrediting body
+    pub fn remove_accrediting_body(env: Env, admin: Address, body: Address) {
+        Self::require_admin(&env, &admin);
+
+        let key = DataKey::AccreditingBody(body.clone());
+        if !env.storage().persistent().has(&key) {
+            panic!("Accrediting body not found");
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("rm_body"), body), symbol_short!("success"));
+    }
+
+    /// Check whether an address is a registered accrediting body
+    pub fn is_accrediting_body(env: Env, body: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::AccreditingBody(body))
+    }
+
+    /// Issue an accreditation or license attestation to a hospital
+    ///
+    /// # Arguments
+    /// * `issuer` - The accrediting body issuing the attestation
+    /// * `wallet` - The wallet address of the hospital
+    /// * `credential_type` - The type of credential being attested
+    /// * `document_hash` - Hash of the supporting accreditation document
+    /// * `expires_at` - Expiration timestamp of the attestation
+    ///
+    /// # Returns
+    /// The id of the new attestation for this hospital
+    pub fn issue_attestation(
+        env: Env,
+        issuer: Address,
+        wallet: Address,
+        credential_type: String,
+        document_hash: BytesN<32>,
+        expires_at: u64,
+    ) -> u32 {
+        issuer.require_auth();
+
+        if !Self::is_accrediting_body(env.clone(), issuer.clone()) {
+            panic!("Issuer is not an accrediting body");
+        }
+        Self::assert_hospital_exists(&env, &wallet);
+
+        let issued_at = env.ledger().timestamp();
+        if expires_at <= issued_at {
+            panic!("Invalid expiration");
+        }
+
+        let key = DataKey::Attestations(wallet.clone());
+        let mut attestations: Vec<Attestation> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        let id = attestations.len();
+        attestations.push_back(Attestation {
+            id,
+            issuer: issuer.clone(),
+            credential_type,
+            document_hash,
+            issued_at,
+            expires_at,
+            revoked: false,
+        });
+        env.storage().persistent().set(&key, &attestations);
+
+        env.events().publish(
+            (symbol_short!("att_iss"), wallet, issuer),
+            symbol_short!("success"),
+        );
+
+        id
+    }
+
+    /// Revoke an attestation. Only the body that issued it may revoke it.
+    ///
+    /// # Arguments
+    /// * `issuer` - The accrediting body that issued the attestation
+    /// * `wallet` - The wallet address of the hospital
+    /// * `attestation_id` - The id of the attestation to revoke
+    pub fn revoke_attestation(env: Env, issuer: Address, wallet: Address, attestation_id: u32) {
+        issuer.require_auth();
+
+        let key = DataKey::Attestations(wallet.clone());
+        let mut attestations: Vec<Attestation> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        let mut attestation = attestations
+            .get(attestation_id)
+            .expect("Attestation not found");
+
+        if attestation.issuer != issuer {
+            panic!("Only the issuer can revoke this attestation");
+        }
+        if attestation.revoked {
+            panic!("Attestation already revoked");
+        }
+
+        attestation.revoked = true;
+        attestations.set(attestation_id, attestation);
+        env.storage().persistent().set(&key, &attestations);
+
+        env.events().publish(
+            (symbol_short!("att_rev"), wallet, issuer),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Retrieve all attestations (including expired and revoked) for a hospital
+    pub fn get_attestations(env: Env, wallet: Address) -> Vec<Attestation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestations(wallet))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check whether a hospital currently holds a valid attestation of a given type
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the hospital
+    /// * `credential_type` - The type of credential to check
+    ///
+    /// # Returns
+    /// `true` if an unrevoked, unexpired attestation of that type exists from a
+    /// body that is still accredited, `false` otherwise
+    pub fn has_valid_attestation(env: Env, wallet: Address, credential_type: String) -> bool {
+        let attestations = Self::get_attestations(env.clone(), wallet);
+        let now = env.ledger().timestamp();
+
+        for attestation in attestations.iter() {
+            if attestation.credential_type == credential_type
+                && !attestation.revoked
+                && attestation.expires_at > now
+                && Self::is_accrediting_body(env.clone(), attestation.issuer.clone())
+            {
+                return true;
+            }
+        }
+        false
+    }
 }
 
 mod test;
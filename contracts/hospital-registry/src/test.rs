@@ -1,11 +1,14 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String, Vec,
+};
 
 #[test]
 fn test_register_hospital() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HospitalRegistry);
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
     let client = HospitalRegistryClient::new(&env, &contract_id);
 
     let hospital_wallet = Address::generate(&env);
@@ -35,7 +38,7 @@ fn test_register_hospital() {
 #[test]
 fn test_update_hospital() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HospitalRegistry);
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
     let client = HospitalRegistryClient::new(&env, &contract_id);
 
     let hospital_wallet = Address::generate(&env);
@@ -67,7 +70,7 @@ fn test_update_hospital() {
 #[should_panic(expected = "Hospital already registered")]
 fn test_duplicate_registration() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HospitalRegistry);
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
     let client = HospitalRegistryClient::new(&env, &contract_id);
 
     let hospital_wallet = Address::generate(&env);
@@ -94,7 +97,7 @@ fn test_duplicate_registration() {
 #[should_panic(expected = "Hospital not found")]
 fn test_get_nonexistent_hospital() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HospitalRegistry);
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
     let client = HospitalRegistryClient::new(&env, &contract_id);
 
     let hospital_wallet = Address::generate(&env);
@@ -106,7 +109,7 @@ fn test_get_nonexistent_hospital() {
 #[should_panic(expected = "Hospital not found")]
 fn test_update_nonexistent_hospital() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HospitalRegistry);
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
     let client = HospitalRegistryClient::new(&env, &contract_id);
 
     let hospital_wallet = Address::generate(&env);
@@ -119,14 +122,44 @@ fn test_update_nonexistent_hospital() {
     );
 }
 
+/// Registers an insurer with a claims contact in a new insurer registry and
+/// points the hospital registry at it
+fn setup_insurer_contact(
+    env: &Env,
+    client: &HospitalRegistryClient,
+    admin: &Address,
+) -> (Address, u32) {
+    let insurers_id = env.register(insurer_registry::InsurerRegistry, (admin.clone(),));
+    let insurers = insurer_registry::InsurerRegistryClient::new(env, &insurers_id);
+    client.set_insurer_registry(admin, &insurers_id);
+
+    let insurer_wallet = Address::generate(env);
+    insurers.register_insurer(
+        &insurer_wallet,
+        &String::from_str(env, "HealthGuard Insurance"),
+        &String::from_str(env, "INS-2026-12345"),
+        &String::from_str(env, ""),
+    );
+    let contact_id = insurers.add_contact(
+        &insurer_wallet,
+        &insurer_registry::ContactType::Claims,
+        &insurer_registry::ContactChannel::Email,
+        &BytesN::from_array(env, &[1; 32]),
+        &String::from_str(env, "Mon-Fri"),
+    );
+    (insurer_wallet, contact_id)
+}
+
 #[test]
 fn test_hospital_config_flow() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HospitalRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(HospitalRegistry, (admin.clone(),));
     let client = HospitalRegistryClient::new(&env, &contract_id);
 
     let hospital_wallet = Address::generate(&env);
     env.mock_all_auths();
+    let (insurer_wallet, contact_id) = setup_insurer_contact(&env, &client, &admin);
 
     client.register_hospital(
         &hospital_wallet,
@@ -184,8 +217,18 @@ fn test_hospital_config_flow() {
     insurance_providers.push_back(InsuranceProviderConfig {
         provider_name: String::from_str(&env, "Acme Health"),
         plan_codes,
-        billing_contact: String::from_str(&env, "billing@acmehealth.com"),
+        billing_contact: Some(String::from_str(&env, "billing@acmehealth.com")),
         metadata: String::from_str(&env, "EDI enabled"),
+        insurer_wallet: None,
+        billing_contact_id: None,
+    });
+    insurance_providers.push_back(InsuranceProviderConfig {
+        provider_name: String::from_str(&env, "HealthGuard Insurance"),
+        plan_codes: Vec::new(&env),
+        billing_contact: None,
+        metadata: String::from_str(&env, ""),
+        insurer_wallet: Some(insurer_wallet),
+        billing_contact_id: Some(contact_id),
     });
 
     let billing = BillingConfig {
@@ -236,3 +279,329 @@ fn test_hospital_config_flow() {
     let stored_after = client.get_hospital_config(&hospital_wallet);
     assert_eq!(stored_after.departments, updated_departments);
 }
+
+fn linked_provider(
+    env: &Env,
+    insurer_wallet: Option<Address>,
+    billing_contact_id: Option<u32>,
+) -> Vec<InsuranceProviderConfig> {
+    vec![
+        env,
+        InsuranceProviderConfig {
+            provider_name: String::from_str(env, "HealthGuard Insurance"),
+            plan_codes: Vec::new(env),
+            billing_contact: None,
+            metadata: String::from_str(env, ""),
+            insurer_wallet,
+            billing_contact_id,
+        },
+    ]
+}
+
+#[test]
+#[should_panic(expected = "Insurer wallet and billing contact id must be set together")]
+fn test_billing_contact_id_requires_insurer_wallet() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(HospitalRegistry, (admin.clone(),));
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    let (_insurer_wallet, contact_id) = setup_insurer_contact(&env, &client, &admin);
+    let hospital = register_with_departments(&env, &client, "A", "US-NY", &[]);
+
+    client.update_insurance_providers(&hospital, &linked_provider(&env, None, Some(contact_id)));
+}
+
+#[test]
+#[should_panic(expected = "Billing contact not found")]
+fn test_billing_contact_must_exist_in_insurer_registry() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(HospitalRegistry, (admin.clone(),));
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    let (insurer_wallet, contact_id) = setup_insurer_contact(&env, &client, &admin);
+    let hospital = register_with_departments(&env, &client, "A", "US-NY", &[]);
+
+    client.update_insurance_providers(
+        &hospital,
+        &linked_provider(&env, Some(insurer_wallet), Some(contact_id + 1)),
+    );
+}
+
+fn setup_accreditation(env: &Env) -> (HospitalRegistryClient<'_>, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(HospitalRegistry, (admin.clone(),));
+    let client = HospitalRegistryClient::new(env, &contract_id);
+
+    let body = Address::generate(env);
+    let hospital_wallet = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.add_accrediting_body(&admin, &body);
+    client.register_hospital(
+        &hospital_wallet,
+        &String::from_str(env, "Regional Medical Center"),
+        &String::from_str(env, "789 Pine Rd"),
+        &String::from_str(env, "Trauma level II"),
+    );
+
+    (client, admin, body, hospital_wallet)
+}
+
+#[test]
+fn test_issue_and_check_attestation() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1000);
+    let (client, _admin, body, hospital_wallet) = setup_accreditation(&env);
+
+    let jci = String::from_str(&env, "JCI");
+    let id = client.issue_attestation(
+        &body,
+        &hospital_wallet,
+        &jci,
+        &BytesN::from_array(&env, &[7; 32]),
+        &5000,
+    );
+    assert_eq!(id, 0);
+
+    let attestations = client.get_attestations(&hospital_wallet);
+    assert_eq!(attestations.len(), 1);
+    let attestation = attestations.get(0).unwrap();
+    assert_eq!(attestation.issuer, body);
+    assert_eq!(attestation.issued_at, 1000);
+    assert_eq!(attestation.expires_at, 5000);
+
+    assert!(client.has_valid_attestation(&hospital_wallet, &jci));
+    assert!(
+        !client.has_valid_attestation(&hospital_wallet, &String::from_str(&env, "STATE_LICENSE"))
+    );
+
+    // Expired attestations no longer count
+    env.ledger().set_timestamp(5000);
+    assert!(!client.has_valid_attestation(&hospital_wallet, &jci));
+}
+
+#[test]
+fn test_revoke_attestation() {
+    let env = Env::default();
+    let (client, _admin, body, hospital_wallet) = setup_accreditation(&env);
+
+    let license = String::from_str(&env, "STATE_LICENSE");
+    let id = client.issue_attestation(
+        &body,
+        &hospital_wallet,
+        &license,
+        &BytesN::from_array(&env, &[1; 32]),
+        &10_000,
+    );
+    assert!(client.has_valid_attestation(&hospital_wallet, &license));
+
+    client.revoke_attestation(&body, &hospital_wallet, &id);
+    assert!(!client.has_valid_attestation(&hospital_wallet, &license));
+    assert!(
+        client
+            .get_attestations(&hospital_wallet)
+            .get(0)
+            .unwrap()
+            .revoked
+    );
+}
+
+#[test]
+fn test_removed_body_attestations_not_valid() {
+    let env = Env::default();
+    let (client, admin, body, hospital_wallet) = setup_accreditation(&env);
+
+    let jci = String::from_str(&env, "JCI");
+    client.issue_attestation(
+        &body,
+        &hospital_wallet,
+        &jci,
+        &BytesN::from_array(&env, &[2; 32]),
+        &10_000,
+    );
+    assert!(client.has_valid_attestation(&hospital_wallet, &jci));
+
+    client.remove_accrediting_body(&admin, &body);
+    assert!(!client.is_accrediting_body(&body));
+    assert!(!client.has_valid_attestation(&hospital_wallet, &jci));
+}
+
+#[test]
+#[should_panic(expected = "Issuer is not an accrediting body")]
+fn test_issue_attestation_unaccredited_issuer() {
+    let env = Env::default();
+    let (client, _admin, _body, hospital_wallet) = setup_accreditation(&env);
+
+    let stranger = Address::generate(&env);
+    client.issue_attestation(
+        &stranger,
+        &hospital_wallet,
+        &String::from_str(&env, "JCI"),
+        &BytesN::from_array(&env, &[3; 32]),
+        &10_000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only the issuer can revoke this attestation")]
+fn test_revoke_attestation_by_other_body() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(HospitalRegistry, (admin.clone(),));
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    let body = Address::generate(&env);
+    let other_body = Address::generate(&env);
+    let hospital_wallet = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.add_accrediting_body(&admin, &body);
+    client.add_accrediting_body(&admin, &other_body);
+    client.register_hospital(
+        &hospital_wallet,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "456 Oak Ave"),
+        &String::from_str(&env, "General Services"),
+    );
+
+    let id = client.issue_attestation(
+        &body,
+        &hospital_wallet,
+        &String::from_str(&env, "JCI"),
+        &BytesN::from_array(&env, &[4; 32]),
+        &10_000,
+    );
+    client.revoke_attestation(&other_body, &hospital_wallet, &id);
+}
+
+#[test]
+#[should_panic(expected = "Only admin can perform this action")]
+fn test_add_accrediting_body_non_admin() {
+    let env = Env::default();
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    let not_admin = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.add_accrediting_body(&not_admin, &Address::generate(&env));
+}
+
+fn register_with_departments(
+    env: &Env,
+    client: &HospitalRegistryClient,
+    name: &str,
+    region: &str,
+    departments: &[&str],
+) -> Address {
+    let wallet = Address::generate(env);
+    client.register_hospital(
+        &wallet,
+        &String::from_str(env, name),
+        &String::from_str(env, "Somewhere"),
+        &String::from_str(env, ""),
+    );
+
+    let mut depts: Vec<Department> = Vec::new(env);
+    for dept in departments {
+        depts.push_back(Department {
+            name: String::from_str(env, dept),
+            head: String::from_str(env, "Dr. Head"),
+            contact: String::from_str(env, "contact@hospital.org"),
+        });
+    }
+    client.update_departments(&wallet, &depts);
+    client.set_hospital_region(&wallet, &String::from_str(env, region));
+    wallet
+}
+
+#[test]
+fn test_list_hospitals_pagination() {
+    let env = Env::default();
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let a = register_with_departments(&env, &client, "A", "US-NY", &["Cardiology"]);
+    let b = register_with_departments(&env, &client, "B", "US-NY", &["Oncology"]);
+    let c = register_with_departments(&env, &client, "C", "US-CA", &["Cardiology"]);
+
+    assert_eq!(client.get_hospital_count(), 3);
+
+    let first_page = client.list_hospitals(&0, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page.get(0).unwrap(), a);
+    assert_eq!(first_page.get(1).unwrap(), b);
+
+    let second_page = client.list_hospitals(&2, &2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap(), c);
+
+    assert_eq!(client.list_hospitals(&5, &2).len(), 0);
+}
+
+#[test]
+fn test_search_hospitals_by_region_and_service() {
+    let env = Env::default();
+    let contract_id = env.register(HospitalRegistry, (Address::generate(&env),));
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let a = register_with_departments(&env, &client, "A", "US-NY", &["Cardiology", "Emergency"]);
+    let b = register_with_departments(&env, &client, "B", "US-NY", &["Oncology"]);
+    let c = register_with_departments(&env, &client, "C", "US-CA", &["Cardiology"]);
+
+    let ny = String::from_str(&env, "US-NY");
+    let cardiology = String::from_str(&env, "Cardiology");
+
+    let in_ny = client.get_hospitals_by_region(&ny, &0, &10);
+    assert_eq!(in_ny.len(), 2);
+
+    let cardio = client.get_hospitals_by_service(&cardiology, &0, &10);
+    assert_eq!(cardio.len(), 2);
+    assert!(cardio.contains(&a));
+    assert!(cardio.contains(&c));
+
+    let ny_cardio = client.search_hospitals(&ny, &cardiology, &0, &10);
+    assert_eq!(ny_cardio.hospitals, vec![&env, a.clone()]);
+    assert_eq!(ny_cardio.next_cursor, None);
+
+    // Moving region and changing departments keeps the indexes in sync
+    client.set_hospital_region(&b, &String::from_str(&env, "US-CA"));
+    assert_eq!(client.get_hospitals_by_region(&ny, &0, &10).len(), 1);
+    assert_eq!(
+        client.get_hospital_region(&b),
+        Some(String::from_str(&env, "US-CA"))
+    );
+
+    client.update_departments(&a, &Vec::new(&env));
+    let cardio = client.get_hospitals_by_service(&cardiology, &0, &10);
+    assert_eq!(cardio.len(), 1);
+    assert_eq!(cardio.get(0).unwrap(), c);
+    assert!(client
+        .search_hospitals(&ny, &cardiology, &0, &10)
+        .hospitals
+        .is_empty());
+
+    // Pages examine at most `limit` entries and resume from the returned cursor
+    let d = register_with_departments(&env, &client, "D", "US-NY", &["Cardiology"]);
+    let e = register_with_departments(&env, &client, "E", "US-NY", &["Cardiology"]);
+    let empty = client.search_hospitals(&ny, &cardiology, &0, &1);
+    assert!(empty.hospitals.is_empty());
+    assert_eq!(empty.next_cursor, Some(1));
+    let first = client.search_hospitals(&ny, &cardiology, &1, &1);
+    assert_eq!(first.hospitals, vec![&env, d]);
+    assert_eq!(first.next_cursor, Some(2));
+    let second = client.search_hospitals(&ny, &cardiology, &2, &1);
+    assert_eq!(second.hospitals, vec![&env, e]);
+    assert_eq!(second.next_cursor, None);
+}
@@ -1,6 +1,9 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, String, Symbol,
+    Vec,
+};
 
 /// --------------------
 /// Insurer Structures
@@ -15,6 +18,215 @@ pub struct InsurerData {
     pub contact_details: String,
     pub coverage_policies: String,
     pub metadata: String,
+    pub status: InsurerStatus,
+    pub status_reason: String,
+    pub license_expiry: u64, // 0 means no expiry recorded
+}
+
+/// Regulatory standing of an insurer's license
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InsurerStatus {
+    PendingVerification,
+    Verified,
+    Suspended,
+}
+
+/// --------------------
+/// Contact Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContactType {
+    Claims,
+    ProviderRelations,
+    MemberServices,
+    FraudReporting,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContactChannel {
+    Phone,
+    Email,
+    Fax,
+    Portal,
+    Mail,
+}
+
+/// A typed contact point. The contact value itself is stored off-chain
+/// encrypted and referenced by hash.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsurerContact {
+    pub contact_id: u32,
+    pub contact_type: ContactType,
+    pub channel: ContactChannel,
+    pub contact_hash: BytesN<32>,
+    pub office_hours: String,
+}
+
+/// --------------------
+/// Plan Structures
+/// --------------------
+/// A published insurance plan with structured coverage rules.
+/// Monetary amounts are in the smallest currency unit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsurancePlan {
+    pub plan_code: String,
+    pub covered_services: Vec<String>,
+    pub copay: i128,
+    pub coinsurance_bps: u32, // Patient share after deductible, 10000 = 100%
+    pub deductible: i128,
+    pub out_of_pocket_max: i128,
+    pub prior_auth_services: Vec<String>,
+    pub effective_from: u64,
+    pub effective_to: u64, // 0 means no end date
+}
+
+/// Coverage terms for a single service code under a plan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoverageDetails {
+    pub covered: bool,
+    pub prior_auth_required: bool,
+    pub copay: i128,
+    pub coinsurance_bps: u32,
+    pub deductible: i128,
+    pub out_of_pocket_max: i128,
+}
+
+/// --------------------
+/// Enrollment Structures
+/// --------------------
+/// A member's enrollment in one of an insurer's plans
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Enrollment {
+    pub member_id: String,
+    pub patient: Address,
+    pub plan_code: String,
+    pub coverage_start: u64,
+    pub coverage_end: u64, // 0 means open-ended
+    pub dependents: Vec<Address>,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CoverageStatus {
+    NotEnrolled,
+    CoverageInactive,
+    ServiceNotCovered,
+    Eligible,
+}
+
+/// Result of a real-time eligibility check
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EligibilityResult {
+    pub status: CoverageStatus,
+    pub member_id: String,
+    pub plan_code: String,
+    pub coverage: CoverageDetails,
+}
+
+/// --------------------
+/// Prior Authorization Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PriorAuthStatus {
+    Requested,
+    Pended,
+    Approved,
+    Denied,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriorAuthorization {
+    pub auth_id: u64,
+    pub insurer: Address,
+    pub provider: Address,
+    pub patient: Address,
+    pub service_code: String,
+    pub requested_units: u32,
+    pub clinical_hash: BytesN<32>,
+    pub status: PriorAuthStatus,
+    pub approved_units: u32,
+    pub used_units: u32,
+    pub valid_from: u64,
+    pub valid_to: u64,
+    pub reason_code: String,
+    pub requested_at: u64,
+    pub decided_at: u64,
+}
+
+/// --------------------
+/// Reviewer Structures
+/// --------------------
+/// A claims reviewer's seniority tier and specialty scope
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewerProfile {
+    pub tier: u32,
+    pub specialties: Vec<String>, // Empty means all specialties
+}
+
+/// --------------------
+/// Claim Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClaimStatus {
+    Submitted,
+    Escalated,
+    InfoRequested,
+    Approved,
+    PartiallyApproved,
+    Denied,
+}
+
+/// A single billed service on a claim
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimLine {
+    pub service_code: String,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub claim_id: u64,
+    pub insurer: Address,
+    pub provider: Address,
+    pub patient: Address,
+    pub specialty: String,
+    pub lines: Vec<ClaimLine>,
+    pub total_amount: i128,
+    pub approved_amount: i128,
+    pub document_hashes: Vec<BytesN<32>>,
+    pub status: ClaimStatus,
+    pub required_tier: u32, // Minimum reviewer tier, raised on escalation
+    pub submitted_at: u64,
+    pub updated_at: u64,
+    pub eob_hash: Option<BytesN<32>>, // Explanation of benefits, set on final adjudication
+    pub primary_claim_id: Option<u64>, // Set on secondary claims created by coordination of benefits
+    pub primary_eob_hash: Option<BytesN<32>>, // Primary payer's explanation of benefits
+}
+
+/// One step in a claim's adjudication history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimStatusEntry {
+    pub status: ClaimStatus,
+    pub actor: Address,
+    pub reason_code: String,
+    pub approved_amount: i128,
+    pub timestamp: u64,
 }
 
 /// --------------------
@@ -24,8 +236,31 @@ pub struct InsurerData {
 pub enum DataKey {
     Insurer(Address),
     ClaimsReviewers(Address), // Maps insurer wallet to list of approved reviewers
+    Plan(Address, String),    // (Insurer, Plan code) -> InsurancePlan
+    PlanCodes(Address),       // Insurer -> Vec<String> of published plan codes
+    ClaimCount,               // Last issued claim id
+    Claim(u64),               // Claim id -> Claim
+    ClaimHistory(u64),        // Claim id -> Vec<ClaimStatusEntry>
+    ProviderClaims(Address),  // Provider -> Vec<u64> of claim ids
+    PatientClaims(Address),   // Patient -> Vec<u64> of claim ids
+    Enrollment(Address, Address), // (Insurer, Subscriber) -> Enrollment
+    Dependent(Address, Address), // (Insurer, Dependent) -> Subscriber
+    PriorAuthCount,           // Last issued prior authorization id
+    PriorAuth(u64),           // Authorization id -> PriorAuthorization
+    PatientPriorAuths(Address), // Patient -> Vec<u64> of authorization ids
+    Admin,
+    Regulator(Address),              // Regulator -> bool
+    Reviewer(Address, Address),      // (Insurer, Reviewer) -> ReviewerProfile
+    ReviewerTiers(Address),          // Insurer -> Map<u32, i128> of tier approval limits
+    Contact(Address, u32),           // (Insurer, Contact id) -> InsurerContact
+    ContactIds(Address),             // Insurer -> Vec<u32> of current contact ids
+    ContactCount(Address),           // Insurer -> last issued contact id
+    Cob(Address),                    // Patient -> Vec<Address> of insurers, primary payer first
+    StatusBeforeSuspension(Address), // Insurer -> InsurerStatus held when suspended
 }
 
+const MAX_BPS: u32 = 10_000;
+
 #[contract]
 pub struct InsurerRegistry;
 
@@ -61,6 +296,9 @@ impl InsurerRegistry {
             contact_details: String::from_str(&env, ""),
             coverage_policies: String::from_str(&env, ""),
             metadata,
+            status: InsurerStatus::PendingVerification,
+            status_reason: String::from_str(&env, ""),
+            license_expiry: 0,
         };
 
         env.storage().persistent().set(&key, &insurer);
@@ -101,6 +339,8 @@ impl InsurerRegistry {
 
     /// Update insurance company contact details
     ///
+    /// Free-text legacy field; use `add_contact` for typed contact channels.
+    ///
     /// # Arguments
     /// * `wallet` - The wallet address of the insurance company
     /// * `contact_details` - Updated contact information (phone, email, address)
@@ -163,6 +403,166 @@ impl InsurerRegistry {
             .expect("Insurer not found")
     }
 
+    // =====================================================
+    //                 CONTACT CHANNELS
+    // =====================================================
+
+    /// Add a typed contact point for the insurance company
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the insurance company
+    /// * `contact_type` - What the contact is for (claims, member services, etc.)
+    /// * `channel` - How to reach it (phone, email, portal, etc.)
+    /// * `contact_hash` - Hash of the encrypted contact value
+    /// * `office_hours` - Hours the contact is staffed
+    ///
+    /// # Returns
+    /// The id of the new contact, which hospitals may reference
+    pub fn add_contact(
+        env: Env,
+        wallet: Address,
+        contact_type: ContactType,
+        channel: ContactChannel,
+        contact_hash: BytesN<32>,
+        office_hours: String,
+    ) -> u32 {
+        wallet.require_auth();
+
+        let insurer_key = DataKey::Insurer(wallet.clone());
+        if !env.storage().persistent().has(&insurer_key) {
+            panic!("Insurer not registered");
+        }
+
+        let count_key = DataKey::ContactCount(wallet.clone());
+        let contact_id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+
+        let contact = InsurerContact {
+            contact_id,
+            contact_type,
+            channel,
+            contact_hash,
+            office_hours,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contact(wallet.clone(), contact_id), &contact);
+        env.storage().persistent().set(&count_key, &contact_id);
+
+        let ids_key = DataKey::ContactIds(wallet.clone());
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&ids_key)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(contact_id);
+        env.storage().persistent().set(&ids_key, &ids);
+
+        env.events()
+            .publish((symbol_short!("add_cnt"), wallet, contact_id), contact_type);
+
+        contact_id
+    }
+
+    /// Update the channel, hash and office hours of an existing contact
+    pub fn update_contact(
+        env: Env,
+        wallet: Address,
+        contact_id: u32,
+        channel: ContactChannel,
+        contact_hash: BytesN<32>,
+        office_hours: String,
+    ) {
+        wallet.require_auth();
+
+        let key = DataKey::Contact(wallet.clone(), contact_id);
+        let mut contact: InsurerContact = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Contact not found");
+
+        contact.channel = channel;
+        contact.contact_hash = contact_hash;
+        contact.office_hours = office_hours;
+        env.storage().persistent().set(&key, &contact);
+
+        env.events().publish(
+            (symbol_short!("upd_cnt"), wallet, contact_id),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Remove a contact. Its id is never reused.
+    pub fn remove_contact(env: Env, wallet: Address, contact_id: u32) {
+        wallet.require_auth();
+
+        let key = DataKey::Contact(wallet.clone(), contact_id);
+        if !env.storage().persistent().has(&key) {
+            panic!("Contact not found");
+        }
+        env.storage().persistent().remove(&key);
+
+        let ids_key = DataKey::ContactIds(wallet.clone());
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&ids_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(i) = ids.first_index_of(contact_id) {
+            ids.remove(i);
+        }
+        env.storage().persistent().set(&ids_key, &ids);
+
+        env.events().publish(
+            (symbol_short!("rm_cnt"), wallet, contact_id),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Retrieve a single contact by id
+    ///
+    /// # Panics
+    /// Panics if the contact is not found
+    pub fn get_contact(env: Env, wallet: Address, contact_id: u32) -> InsurerContact {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contact(wallet, contact_id))
+            .expect("Contact not found")
+    }
+
+    /// Check whether an insurer has a current (not removed) contact with this id
+    pub fn has_contact(env: Env, wallet: Address, contact_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Contact(wallet, contact_id))
+    }
+
+    /// Get all contacts of an insurer, optionally filtered by type
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the insurance company
+    /// * `contact_type` - Only return contacts of this type, if provided
+    pub fn get_contacts(
+        env: Env,
+        wallet: Address,
+        contact_type: Option<ContactType>,
+    ) -> Vec<InsurerContact> {
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContactIds(wallet.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        let mut contacts = Vec::new(&env);
+        for id in ids.iter() {
+            let contact = Self::get_contact(env.clone(), wallet.clone(), id);
+            if contact_type.is_none() || contact_type == Some(contact.contact_type) {
+                contacts.push_back(contact);
+            }
+        }
+        contacts
+    }
+
     // =====================================================
     //            CLAIMS REVIEWERS MANAGEMENT
     // =====================================================
@@ -183,6 +583,20 @@ impl InsurerRegistry {
         if !env.storage().persistent().has(&insurer_key) {
             panic!("Insurer not registered");
         }
+        Self::assert_insurer_active(&env, &insurer_wallet);
+
+        // Check if reviewer already exists
+        let reviewer_key = DataKey::Reviewer(insurer_wallet.clone(), reviewer_wallet.clone());
+        if env.storage().persistent().has(&reviewer_key) {
+            panic!("Reviewer already authorized");
+        }
+
+        // New reviewers start at the entry tier with no specialty restriction
+        let profile = ReviewerProfile {
+            tier: 1,
+            specialties: Vec::new(&env),
+        };
+        env.storage().persistent().set(&reviewer_key, &profile);
 
         let reviewers_key = DataKey::ClaimsReviewers(insurer_wallet.clone());
         let mut reviewers: Vec<Address> = env
@@ -190,14 +604,6 @@ impl InsurerRegistry {
             .persistent()
             .get(&reviewers_key)
             .unwrap_or(Vec::new(&env));
-
-        // Check if reviewer already exists
-        for i in 0..reviewers.len() {
-            if reviewers.get(i).unwrap() == reviewer_wallet {
-                panic!("Reviewer already authorized");
-            }
-        }
-
         reviewers.push_back(reviewer_wallet.clone());
         env.storage().persistent().set(&reviewers_key, &reviewers);
 
@@ -219,31 +625,22 @@ impl InsurerRegistry {
         insurer_wallet.require_auth();
 
         let reviewers_key = DataKey::ClaimsReviewers(insurer_wallet.clone());
-        let reviewers: Vec<Address> = env
+        let mut reviewers: Vec<Address> = env
             .storage()
             .persistent()
             .get(&reviewers_key)
             .expect("No reviewers found");
 
-        let mut new_reviewers: Vec<Address> = Vec::new(&env);
-        let mut found = false;
-
-        for i in 0..reviewers.len() {
-            let reviewer = reviewers.get(i).unwrap();
-            if reviewer != reviewer_wallet {
-                new_reviewers.push_back(reviewer);
-            } else {
-                found = true;
-            }
-        }
-
-        if !found {
+        let reviewer_key = DataKey::Reviewer(insurer_wallet.clone(), reviewer_wallet.clone());
+        if !env.storage().persistent().has(&reviewer_key) {
             panic!("Reviewer not found");
         }
+        env.storage().persistent().remove(&reviewer_key);
 
-        env.storage()
-            .persistent()
-            .set(&reviewers_key, &new_reviewers);
+        if let Some(i) = reviewers.first_index_of(&reviewer_wallet) {
+            reviewers.remove(i);
+        }
+        env.storage().persistent().set(&reviewers_key, &reviewers);
 
         env.events().publish(
             (symbol_short!("rm_rev"), insurer_wallet, reviewer_wallet),
@@ -279,19 +676,1463 @@ impl InsurerRegistry {
         insurer_wallet: Address,
         reviewer_wallet: Address,
     ) -> bool {
-        let reviewers_key = DataKey::ClaimsReviewers(insurer_wallet);
-        let reviewers: Vec<Address> = env
+        env.storage()
+            .persistent()
+            .has(&DataKey::Reviewer(insurer_wallet, reviewer_wallet))
+    }
+
+    /// Set the monetary approval limit for a reviewer tier. Higher tier numbers
+    /// are more senior; a tier without a configured limit may approve any amount.
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `tier` - The tier level (1 is the entry tier)
+    /// * `approval_limit` - Largest amount a reviewer of this tier may approve
+    pub fn set_reviewer_tier(env: Env, insurer_wallet: Address, tier: u32, approval_limit: i128) {
+        insurer_wallet.require_auth();
+        Self::assert_insurer_active(&env, &insurer_wallet);
+
+        if tier == 0 {
+            panic!("Invalid tier");
+        }
+        if approval_limit < 0 {
+            panic!("Invalid approval limit");
+        }
+
+        let key = DataKey::ReviewerTiers(insurer_wallet.clone());
+        let mut tiers: Map<u32, i128> = env
             .storage()
             .persistent()
-            .get(&reviewers_key)
-            .unwrap_or(Vec::new(&env));
+            .get(&key)
+            .unwrap_or(Map::new(&env));
+        tiers.set(tier, approval_limit);
+        env.storage().persistent().set(&key, &tiers);
 
-        for i in 0..reviewers.len() {
-            if reviewers.get(i).unwrap() == reviewer_wallet {
-                return true;
-            }
+        env.events().publish(
+            (symbol_short!("set_tier"), insurer_wallet, tier),
+            approval_limit,
+        );
+    }
+
+    /// Get the configured approval limit for each reviewer tier
+    pub fn get_reviewer_tiers(env: Env, insurer_wallet: Address) -> Map<u32, i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReviewerTiers(insurer_wallet))
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Assign a tier and specialty scope to an existing reviewer
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `reviewer_wallet` - The reviewer to update
+    /// * `tier` - The reviewer's tier level
+    /// * `specialties` - Specialties the reviewer may adjudicate (empty for all)
+    pub fn set_reviewer_role(
+        env: Env,
+        insurer_wallet: Address,
+        reviewer_wallet: Address,
+        tier: u32,
+        specialties: Vec<String>,
+    ) {
+        insurer_wallet.require_auth();
+        Self::assert_insurer_active(&env, &insurer_wallet);
+
+        if tier == 0 {
+            panic!("Invalid tier");
         }
-        false
+
+        let key = DataKey::Reviewer(insurer_wallet.clone(), reviewer_wallet.clone());
+        if !env.storage().persistent().has(&key) {
+            panic!("Reviewer not found");
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &ReviewerProfile { tier, specialties });
+
+        env.events().publish(
+            (symbol_short!("set_role"), insurer_wallet, reviewer_wallet),
+            tier,
+        );
+    }
+
+    /// Retrieve a reviewer's tier and specialty scope
+    ///
+    /// # Panics
+    /// Panics if the reviewer is not authorized for the insurer
+    pub fn get_reviewer_profile(
+        env: Env,
+        insurer_wallet: Address,
+        reviewer_wallet: Address,
+    ) -> ReviewerProfile {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reviewer(insurer_wallet, reviewer_wallet))
+            .expect("Reviewer not found")
+    }
+
+    // =====================================================
+    //                 PLAN CATALOGUE
+    // =====================================================
+
+    /// Publish a new plan or replace an existing plan with the same code
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `plan` - The structured plan definition
+    ///
+    /// # Panics
+    /// Panics if the insurer is not registered or the plan terms are invalid
+    pub fn publish_plan(env: Env, insurer_wallet: Address, plan: InsurancePlan) {
+        insurer_wallet.require_auth();
+
+        let insurer_key = DataKey::Insurer(insurer_wallet.clone());
+        if !env.storage().persistent().has(&insurer_key) {
+            panic!("Insurer not registered");
+        }
+
+        if plan.coinsurance_bps > MAX_BPS {
+            panic!("Invalid coinsurance");
+        }
+        if plan.copay < 0 || plan.deductible < 0 || plan.out_of_pocket_max < 0 {
+            panic!("Invalid plan amounts");
+        }
+        if plan.effective_to != 0 && plan.effective_to <= plan.effective_from {
+            panic!("Invalid effective date range");
+        }
+
+        let plan_code = plan.plan_code.clone();
+        let plan_key = DataKey::Plan(insurer_wallet.clone(), plan_code.clone());
+        if !env.storage().persistent().has(&plan_key) {
+            let codes_key = DataKey::PlanCodes(insurer_wallet.clone());
+            let mut codes: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&codes_key)
+                .unwrap_or(Vec::new(&env));
+            codes.push_back(plan_code.clone());
+            env.storage().persistent().set(&codes_key, &codes);
+        }
+
+        env.storage().persistent().set(&plan_key, &plan);
+
+        env.events().publish(
+            (symbol_short!("pub_plan"), insurer_wallet, plan_code),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Retrieve a published plan
+    ///
+    /// # Panics
+    /// Panics if the plan is not found
+    pub fn get_plan(env: Env, insurer_wallet: Address, plan_code: String) -> InsurancePlan {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Plan(insurer_wallet, plan_code))
+            .expect("Plan not found")
+    }
+
+    /// Get the codes of all plans published by an insurer
+    pub fn get_plan_codes(env: Env, insurer_wallet: Address) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlanCodes(insurer_wallet))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Look up coverage terms for a service code under a plan
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `plan_code` - The plan to look up
+    /// * `service_code` - The service code (e.g. CPT) to check
+    ///
+    /// # Returns
+    /// Coverage details; `covered` is false if the service is not listed or the
+    /// plan is not in effect at the current ledger time
+    pub fn get_coverage(
+        env: Env,
+        insurer_wallet: Address,
+        plan_code: String,
+        service_code: String,
+    ) -> CoverageDetails {
+        let plan = Self::get_plan(env.clone(), insurer_wallet, plan_code);
+        Self::coverage_for(&plan, &service_code, env.ledger().timestamp())
+    }
+
+    fn plan_in_effect(plan: &InsurancePlan, date: u64) -> bool {
+        date >= plan.effective_from && (plan.effective_to == 0 || date < plan.effective_to)
+    }
+
+    fn coverage_for(plan: &InsurancePlan, service_code: &String, date: u64) -> CoverageDetails {
+        let covered =
+            Self::plan_in_effect(plan, date) && plan.covered_services.contains(service_code);
+
+        CoverageDetails {
+            covered,
+            prior_auth_required: covered && plan.prior_auth_services.contains(service_code),
+            copay: plan.copay,
+            coinsurance_bps: plan.coinsurance_bps,
+            deductible: plan.deductible,
+            out_of_pocket_max: plan.out_of_pocket_max,
+        }
+    }
+
+    // =====================================================
+    //             CLAIMS SUBMISSION & ADJUDICATION
+    // =====================================================
+
+    /// Submit a claim to an insurer
+    ///
+    /// # Arguments
+    /// * `provider` - The wallet address of the submitting provider
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `patient` - The wallet address of the patient
+    /// * `specialty` - The clinical specialty of the claim, used to route it to reviewers
+    /// * `lines` - The billed services and amounts
+    /// * `document_hashes` - Hashes of supporting documents
+    ///
+    /// # Returns
+    /// The id of the new claim
+    pub fn submit_claim(
+        env: Env,
+        provider: Address,
+        insurer_wallet: Address,
+        patient: Address,
+        specialty: String,
+        lines: Vec<ClaimLine>,
+        document_hashes: Vec<BytesN<32>>,
+    ) -> u64 {
+        provider.require_auth();
+
+        let insurer_key = DataKey::Insurer(insurer_wallet.clone());
+        if !env.storage().persistent().has(&insurer_key) {
+            panic!("Insurer not registered");
+        }
+        if lines.is_empty() {
+            panic!("Claim has no lines");
+        }
+
+        let mut total_amount: i128 = 0;
+        for line in lines.iter() {
+            if line.amount <= 0 {
+                panic!("Invalid claim amount");
+            }
+            total_amount += line.amount;
+        }
+
+        let now = env.ledger().timestamp();
+        let claim = Claim {
+            claim_id: 0,
+            insurer: insurer_wallet,
+            provider: provider.clone(),
+            patient,
+            specialty,
+            lines,
+            total_amount,
+            approved_amount: 0,
+            document_hashes,
+            status: ClaimStatus::Submitted,
+            required_tier: 0,
+            submitted_at: now,
+            updated_at: now,
+            eob_hash: None,
+            primary_claim_id: None,
+            primary_eob_hash: None,
+        };
+
+        Self::store_new_claim(&env, claim, provider)
+    }
+
+    /// Approve a claim in full
+    ///
+    /// # Arguments
+    /// * `reviewer` - An authorized claims reviewer of the claim's insurer
+    /// * `claim_id` - The claim to approve
+    /// * `reason_code` - Adjudication reason code
+    /// * `eob_hash` - Hash of the explanation of benefits document
+    pub fn approve_claim(
+        env: Env,
+        reviewer: Address,
+        claim_id: u64,
+        reason_code: String,
+        eob_hash: BytesN<32>,
+    ) {
+        let claim = Self::get_claim(env.clone(), claim_id);
+        let amount = claim.total_amount;
+        Self::adjudicate(
+            &env,
+            reviewer,
+            claim,
+            ClaimStatus::Approved,
+            amount,
+            reason_code,
+            Some(eob_hash),
+        );
+    }
+
+    /// Approve part of a claim's billed amount
+    ///
+    /// # Arguments
+    /// * `reviewer` - An authorized claims reviewer of the claim's insurer
+    /// * `claim_id` - The claim to approve
+    /// * `approved_amount` - The allowed amount, less than the billed total
+    /// * `reason_code` - Adjudication reason code
+    /// * `eob_hash` - Hash of the explanation of benefits document
+    pub fn partially_approve_claim(
+        env: Env,
+        reviewer: Address,
+        claim_id: u64,
+        approved_amount: i128,
+        reason_code: String,
+        eob_hash: BytesN<32>,
+    ) {
+        let claim = Self::get_claim(env.clone(), claim_id);
+        if approved_amount <= 0 || approved_amount >= claim.total_amount {
+            panic!("Invalid approved amount");
+        }
+        Self::adjudicate(
+            &env,
+            reviewer,
+            claim,
+            ClaimStatus::PartiallyApproved,
+            approved_amount,
+            reason_code,
+            Some(eob_hash),
+        );
+    }
+
+    /// Deny a claim
+    ///
+    /// # Arguments
+    /// * `reviewer` - An authorized claims reviewer of the claim's insurer
+    /// * `claim_id` - The claim to deny
+    /// * `reason_code` - Adjudication reason code
+    /// * `eob_hash` - Hash of the explanation of benefits document
+    pub fn deny_claim(
+        env: Env,
+        reviewer: Address,
+        claim_id: u64,
+        reason_code: String,
+        eob_hash: BytesN<32>,
+    ) {
+        let claim = Self::get_claim(env.clone(), claim_id);
+        Self::adjudicate(
+            &env,
+            reviewer,
+            claim,
+            ClaimStatus::Denied,
+            0,
+            reason_code,
+            Some(eob_hash),
+        );
+    }
+
+    /// Ask the provider for more information before deciding on a claim
+    ///
+    /// # Arguments
+    /// * `reviewer` - An authorized claims reviewer of the claim's insurer
+    /// * `claim_id` - The claim needing more information
+    /// * `reason_code` - Code describing the information requested
+    pub fn request_claim_info(env: Env, reviewer: Address, claim_id: u64, reason_code: String) {
+        let claim = Self::get_claim(env.clone(), claim_id);
+        Self::adjudicate(
+            &env,
+            reviewer,
+            claim,
+            ClaimStatus::InfoRequested,
+            0,
+            reason_code,
+            None,
+        );
+    }
+
+    /// Provide additional documents for a claim awaiting information,
+    /// returning it to the review queue
+    ///
+    /// # Arguments
+    /// * `provider` - The provider that submitted the claim
+    /// * `claim_id` - The claim to update
+    /// * `document_hashes` - Hashes of the additional documents
+    pub fn respond_to_info_request(
+        env: Env,
+        provider: Address,
+        claim_id: u64,
+        document_hashes: Vec<BytesN<32>>,
+    ) {
+        provider.require_auth();
+
+        let mut claim = Self::get_claim(env.clone(), claim_id);
+        if claim.provider != provider {
+            panic!("Not the submitting provider");
+        }
+        if claim.status != ClaimStatus::InfoRequested {
+            panic!("Claim is not awaiting information");
+        }
+
+        claim.document_hashes.append(&document_hashes);
+        Self::record_claim_status(
+            &env,
+            &mut claim,
+            ClaimStatus::Submitted,
+            provider,
+            String::from_str(&env, ""),
+        );
+    }
+
+    /// Retrieve a claim by id
+    ///
+    /// # Panics
+    /// Panics if the claim is not found
+    pub fn get_claim(env: Env, claim_id: u64) -> Claim {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claim(claim_id))
+            .expect("Claim not found")
+    }
+
+    /// Get the full status history of a claim, oldest first
+    pub fn get_claim_history(env: Env, claim_id: u64) -> Vec<ClaimStatusEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimHistory(claim_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the ids of all claims submitted by a provider
+    pub fn get_provider_claims(env: Env, provider: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProviderClaims(provider))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the ids of all claims filed for a patient
+    pub fn get_patient_claims(env: Env, patient: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PatientClaims(patient))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Assign an id to a new claim and persist it with its initial history entry
+    fn store_new_claim(env: &Env, mut claim: Claim, actor: Address) -> u64 {
+        let claim_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimCount)
+            .unwrap_or(0)
+            + 1;
+        claim.claim_id = claim_id;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(claim_id), &claim);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ClaimCount, &claim_id);
+
+        let mut history: Vec<ClaimStatusEntry> = Vec::new(env);
+        history.push_back(ClaimStatusEntry {
+            status: ClaimStatus::Submitted,
+            actor,
+            reason_code: String::from_str(env, ""),
+            approved_amount: 0,
+            timestamp: claim.submitted_at,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::ClaimHistory(claim_id), &history);
+
+        Self::index_claim(env, DataKey::ProviderClaims(claim.provider), claim_id);
+        Self::index_claim(env, DataKey::PatientClaims(claim.patient), claim_id);
+
+        env.events().publish(
+            (symbol_short!("clm_sub"), claim.insurer, claim_id),
+            symbol_short!("success"),
+        );
+
+        claim_id
+    }
+
+    fn index_claim(env: &Env, key: DataKey, claim_id: u64) {
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(claim_id);
+        env.storage().persistent().set(&key, &ids);
+    }
+
+    /// Apply a reviewer decision. Final decisions (approved, partially approved,
+    /// denied) carry an EOB and pass any remaining balance to the next payer.
+    #[allow(clippy::too_many_arguments)]
+    fn adjudicate(
+        env: &Env,
+        reviewer: Address,
+        mut claim: Claim,
+        status: ClaimStatus,
+        approved_amount: i128,
+        reason_code: String,
+        eob_hash: Option<BytesN<32>>,
+    ) {
+        Self::require_reviewer(env, &claim.insurer, &reviewer, &reason_code);
+        if claim.status != ClaimStatus::Submitted && claim.status != ClaimStatus::Escalated {
+            panic!("Claim is not pending review");
+        }
+
+        let profile =
+            Self::get_reviewer_profile(env.clone(), claim.insurer.clone(), reviewer.clone());
+        if profile.tier < claim.required_tier {
+            panic!("Claim requires a higher reviewer tier");
+        }
+        if !profile.specialties.is_empty() && !profile.specialties.contains(&claim.specialty) {
+            panic!("Claim outside reviewer specialty scope");
+        }
+
+        // Approvals above the reviewer's limit are routed to a more senior tier
+        let approves = status == ClaimStatus::Approved || status == ClaimStatus::PartiallyApproved;
+        let tiers = Self::get_reviewer_tiers(env.clone(), claim.insurer.clone());
+        if approves && approved_amount > tiers.get(profile.tier).unwrap_or(i128::MAX) {
+            claim.required_tier = Self::escalation_tier(&tiers, profile.tier, approved_amount);
+            Self::record_claim_status(
+                env,
+                &mut claim,
+                ClaimStatus::Escalated,
+                reviewer,
+                reason_code,
+            );
+            return;
+        }
+
+        claim.approved_amount = approved_amount;
+        if eob_hash.is_none() {
+            Self::record_claim_status(env, &mut claim, status, reviewer, reason_code);
+            return;
+        }
+        claim.eob_hash = eob_hash;
+        Self::record_claim_status(env, &mut claim, status, reviewer, reason_code);
+        Self::create_secondary_claim(env, &claim);
+    }
+
+    /// Bill the remaining balance of an adjudicated claim to the patient's next payer,
+    /// submitted on behalf of the primary claim's provider
+    fn create_secondary_claim(env: &Env, primary: &Claim) {
+        let remaining = primary.total_amount - primary.approved_amount;
+        if remaining <= 0 {
+            return;
+        }
+
+        let payers = Self::get_coordination_of_benefits(env.clone(), primary.patient.clone());
+        let next_payer = match payers.first_index_of(&primary.insurer) {
+            Some(index) => payers.get(index + 1),
+            None => None,
+        };
+        let secondary_insurer = match next_payer {
+            Some(insurer) => insurer,
+            None => return,
+        };
+
+        // The primary's allowed amount is applied to lines in billing order;
+        // the secondary is billed only for what each line has left unpaid
+        let mut paid = primary.approved_amount;
+        let mut lines = Vec::new(env);
+        for mut line in primary.lines.iter() {
+            let covered = line.amount.min(paid);
+            paid -= covered;
+            if line.amount > covered {
+                line.amount -= covered;
+                lines.push_back(line);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        let secondary = Claim {
+            claim_id: 0,
+            insurer: secondary_insurer.clone(),
+            provider: primary.provider.clone(),
+            patient: primary.patient.clone(),
+            specialty: primary.specialty.clone(),
+            lines,
+            total_amount: remaining,
+            approved_amount: 0,
+            document_hashes: primary.document_hashes.clone(),
+            status: ClaimStatus::Submitted,
+            required_tier: 0,
+            submitted_at: now,
+            updated_at: now,
+            eob_hash: None,
+            primary_claim_id: Some(primary.claim_id),
+            primary_eob_hash: primary.eob_hash.clone(),
+        };
+
+        let claim_id = Self::store_new_claim(env, secondary, primary.provider.clone());
+        env.events().publish(
+            (symbol_short!("clm_cob"), secondary_insurer, claim_id),
+            primary.claim_id,
+        );
+    }
+
+    /// Lowest tier above `current` whose limit covers `amount`, falling back to
+    /// the most senior configured tier. Panics if no tier above `current` is configured.
+    fn escalation_tier(tiers: &Map<u32, i128>, current: u32, amount: i128) -> u32 {
+        let mut most_senior = None;
+        for (tier, limit) in tiers.iter() {
+            if tier <= current {
+                continue;
+            }
+            if limit >= amount {
+                return tier;
+            }
+            most_senior = Some(tier);
+        }
+        most_senior.expect("No higher reviewer tier configured")
+    }
+
+    fn require_reviewer(
+        env: &Env,
+        insurer_wallet: &Address,
+        reviewer: &Address,
+        reason_code: &String,
+    ) {
+        reviewer.require_auth();
+
+        Self::assert_insurer_active(env, insurer_wallet);
+        if !Self::is_authorized_reviewer(env.clone(), insurer_wallet.clone(), reviewer.clone()) {
+            panic!("Reviewer not authorized");
+        }
+        if reason_code.is_empty() {
+            panic!("Reason code required");
+        }
+    }
+
+    fn record_claim_status(
+        env: &Env,
+        claim: &mut Claim,
+        status: ClaimStatus,
+        actor: Address,
+        reason_code: String,
+    ) {
+        let now = env.ledger().timestamp();
+        claim.status = status;
+        claim.updated_at = now;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(claim.claim_id), claim);
+
+        let history_key = DataKey::ClaimHistory(claim.claim_id);
+        let mut history: Vec<ClaimStatusEntry> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(env));
+        history.push_back(ClaimStatusEntry {
+            status,
+            actor,
+            reason_code,
+            approved_amount: claim.approved_amount,
+            timestamp: now,
+        });
+        env.storage().persistent().set(&history_key, &history);
+
+        env.events().publish(
+            (
+                symbol_short!("clm_upd"),
+                claim.insurer.clone(),
+                claim.claim_id,
+            ),
+            status,
+        );
+    }
+
+    // =====================================================
+    //             MEMBER ENROLLMENT & ELIGIBILITY
+    // =====================================================
+
+    /// Enroll a member (subscriber) and their dependents in a plan
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `patient` - The subscriber's wallet address
+    /// * `member_id` - The insurer-issued member id
+    /// * `plan_code` - A plan published by this insurer
+    /// * `coverage_start` - Coverage start timestamp
+    /// * `coverage_end` - Coverage end timestamp (0 for open-ended)
+    /// * `dependents` - Dependents covered under the subscriber
+    #[allow(clippy::too_many_arguments)]
+    pub fn enroll_member(
+        env: Env,
+        insurer_wallet: Address,
+        patient: Address,
+        member_id: String,
+        plan_code: String,
+        coverage_start: u64,
+        coverage_end: u64,
+        dependents: Vec<Address>,
+    ) {
+        insurer_wallet.require_auth();
+
+        let plan_key = DataKey::Plan(insurer_wallet.clone(), plan_code.clone());
+        if !env.storage().persistent().has(&plan_key) {
+            panic!("Plan not found");
+        }
+        if coverage_end != 0 && coverage_end <= coverage_start {
+            panic!("Invalid coverage period");
+        }
+
+        let key = DataKey::Enrollment(insurer_wallet.clone(), patient.clone());
+        if let Some(existing) = env.storage().persistent().get::<_, Enrollment>(&key) {
+            if existing.active {
+                panic!("Member already enrolled");
+            }
+            Self::unlink_dependents(&env, &insurer_wallet, &patient, &existing.dependents);
+        }
+
+        Self::link_dependents(&env, &insurer_wallet, &patient, &dependents);
+
+        let enrollment = Enrollment {
+            member_id,
+            patient: patient.clone(),
+            plan_code,
+            coverage_start,
+            coverage_end,
+            dependents,
+            active: true,
+        };
+        env.storage().persistent().set(&key, &enrollment);
+
+        env.events().publish(
+            (symbol_short!("enroll"), insurer_wallet, patient),
+            enrollment,
+        );
+    }
+
+    /// Move an enrolled member to another plan or change their coverage end
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `patient` - The subscriber's wallet address
+    /// * `plan_code` - The new plan code
+    /// * `coverage_end` - The new coverage end timestamp (0 for open-ended)
+    pub fn update_enrollment(
+        env: Env,
+        insurer_wallet: Address,
+        patient: Address,
+        plan_code: String,
+        coverage_end: u64,
+    ) {
+        insurer_wallet.require_auth();
+
+        let plan_key = DataKey::Plan(insurer_wallet.clone(), plan_code.clone());
+        if !env.storage().persistent().has(&plan_key) {
+            panic!("Plan not found");
+        }
+
+        let mut enrollment = Self::get_active_enrollment(&env, &insurer_wallet, &patient);
+        if coverage_end != 0 && coverage_end <= enrollment.coverage_start {
+            panic!("Invalid coverage period");
+        }
+
+        enrollment.plan_code = plan_code;
+        enrollment.coverage_end = coverage_end;
+        Self::save_enrollment(&env, &insurer_wallet, enrollment, symbol_short!("enr_upd"));
+    }
+
+    /// Replace the dependents covered under a subscriber
+    pub fn update_dependents(
+        env: Env,
+        insurer_wallet: Address,
+        patient: Address,
+        dependents: Vec<Address>,
+    ) {
+        insurer_wallet.require_auth();
+
+        let mut enrollment = Self::get_active_enrollment(&env, &insurer_wallet, &patient);
+        Self::unlink_dependents(&env, &insurer_wallet, &patient, &enrollment.dependents);
+        Self::link_dependents(&env, &insurer_wallet, &patient, &dependents);
+
+        enrollment.dependents = dependents;
+        Self::save_enrollment(&env, &insurer_wallet, enrollment, symbol_short!("enr_dep"));
+    }
+
+    /// Terminate a member's enrollment, including their dependents' coverage.
+    /// Dependents stay linked, so dates inside the past coverage period are
+    /// still eligible.
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `patient` - The subscriber's wallet address
+    /// * `coverage_end` - The timestamp coverage ends
+    pub fn terminate_enrollment(
+        env: Env,
+        insurer_wallet: Address,
+        patient: Address,
+        coverage_end: u64,
+    ) {
+        insurer_wallet.require_auth();
+
+        let mut enrollment = Self::get_active_enrollment(&env, &insurer_wallet, &patient);
+        if coverage_end == 0 || coverage_end < enrollment.coverage_start {
+            panic!("Invalid coverage period");
+        }
+
+        enrollment.active = false;
+        enrollment.coverage_end = coverage_end;
+        Self::save_enrollment(&env, &insurer_wallet, enrollment, symbol_short!("enr_term"));
+    }
+
+    /// Retrieve a subscriber's enrollment with an insurer
+    ///
+    /// # Panics
+    /// Panics if the member has never been enrolled
+    pub fn get_enrollment(env: Env, insurer_wallet: Address, patient: Address) -> Enrollment {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Enrollment(insurer_wallet, patient))
+            .expect("Enrollment not found")
+    }
+
+    /// Real-time eligibility check for a patient and service on a given date.
+    /// The patient's own enrollment is checked first; dependents are checked
+    /// against their subscriber's enrollment.
+    ///
+    /// # Arguments
+    /// * `insurer_wallet` - The wallet address of the insurance company
+    /// * `patient` - The subscriber or dependent being treated
+    /// * `service_code` - The service to be rendered
+    /// * `date` - The date of service
+    ///
+    /// # Returns
+    /// The coverage status and the plan's cost-sharing terms for the service
+    pub fn check_eligibility(
+        env: Env,
+        insurer_wallet: Address,
+        patient: Address,
+        service_code: String,
+        date: u64,
+    ) -> EligibilityResult {
+        let own: Option<Enrollment> = env.storage().persistent().get(&DataKey::Enrollment(
+            insurer_wallet.clone(),
+            patient.clone(),
+        ));
+        let enrollment = match own {
+            Some(own) if Self::in_coverage_period(&own, date) => Some(own),
+            own => {
+                let subscriber: Option<Address> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Dependent(insurer_wallet.clone(), patient.clone()));
+                let as_dependent: Option<Enrollment> = subscriber.and_then(|subscriber| {
+                    env.storage()
+                        .persistent()
+                        .get(&DataKey::Enrollment(insurer_wallet.clone(), subscriber))
+                });
+                match as_dependent {
+                    Some(enrollment) if Self::in_coverage_period(&enrollment, date) => {
+                        Some(enrollment)
+                    }
+                    as_dependent => own.or(as_dependent),
+                }
+            }
+        };
+
+        let enrollment = match enrollment {
+            Some(enrollment) => enrollment,
+            None => {
+                return EligibilityResult {
+                    status: CoverageStatus::NotEnrolled,
+                    member_id: String::from_str(&env, ""),
+                    plan_code: String::from_str(&env, ""),
+                    coverage: CoverageDetails {
+                        covered: false,
+                        prior_auth_required: false,
+                        copay: 0,
+                        coinsurance_bps: 0,
+                        deductible: 0,
+                        out_of_pocket_max: 0,
+                    },
+                }
+            }
+        };
+
+        let plan = Self::get_plan(env.clone(), insurer_wallet, enrollment.plan_code.clone());
+        let coverage = Self::coverage_for(&plan, &service_code, date);
+
+        let status =
+            if !Self::in_coverage_period(&enrollment, date) || !Self::plan_in_effect(&plan, date) {
+                CoverageStatus::CoverageInactive
+            } else if !coverage.covered {
+                CoverageStatus::ServiceNotCovered
+            } else {
+                CoverageStatus::Eligible
+            };
+
+        EligibilityResult {
+            status,
+            member_id: enrollment.member_id,
+            plan_code: enrollment.plan_code,
+            coverage,
+        }
+    }
+
+    fn in_coverage_period(enrollment: &Enrollment, date: u64) -> bool {
+        date >= enrollment.coverage_start
+            && (enrollment.coverage_end == 0 || date < enrollment.coverage_end)
+    }
+
+    fn get_active_enrollment(env: &Env, insurer_wallet: &Address, patient: &Address) -> Enrollment {
+        let enrollment: Enrollment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Enrollment(
+                insurer_wallet.clone(),
+                patient.clone(),
+            ))
+            .expect("Enrollment not found");
+        if !enrollment.active {
+            panic!("Enrollment is not active");
+        }
+        enrollment
+    }
+
+    fn save_enrollment(env: &Env, insurer_wallet: &Address, enrollment: Enrollment, event: Symbol) {
+        let patient = enrollment.patient.clone();
+        env.storage().persistent().set(
+            &DataKey::Enrollment(insurer_wallet.clone(), patient.clone()),
+            &enrollment,
+        );
+        env.events()
+            .publish((event, insurer_wallet.clone(), patient), enrollment);
+    }
+
+    fn link_dependents(
+        env: &Env,
+        insurer_wallet: &Address,
+        subscriber: &Address,
+        dependents: &Vec<Address>,
+    ) {
+        for dependent in dependents.iter() {
+            let key = DataKey::Dependent(insurer_wallet.clone(), dependent);
+            // A link to a terminated enrollment can be taken over
+            let linked: Option<Address> = env.storage().persistent().get(&key);
+            if let Some(linked) = linked {
+                let enrollment: Enrollment = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Enrollment(insurer_wallet.clone(), linked))
+                    .expect("Enrollment not found");
+                if enrollment.active {
+                    panic!("Dependent already covered");
+                }
+            }
+            env.storage().persistent().set(&key, subscriber);
+        }
+    }
+
+    fn unlink_dependents(
+        env: &Env,
+        insurer_wallet: &Address,
+        subscriber: &Address,
+        dependents: &Vec<Address>,
+    ) {
+        for dependent in dependents.iter() {
+            let key = DataKey::Dependent(insurer_wallet.clone(), dependent);
+            // Leave links another subscriber has since taken over
+            if env.storage().persistent().get(&key).as_ref() == Some(subscriber) {
+                env.storage().persistent().remove(&key);
+            }
+        }
+    }
+
+    // =====================================================
+    //             COORDINATION OF BENEFITS
+    // =====================================================
+
+    /// Record the order in which a patient's insurers pay claims.
+    /// When a claim is adjudicated by one payer, any unpaid balance is
+    /// automatically submitted to the next payer in this list.
+    ///
+    /// # Arguments
+    /// * `patient` - The patient's wallet address
+    /// * `insurers` - Insurers covering the patient, primary payer first
+    pub fn set_coordination_of_benefits(env: Env, patient: Address, insurers: Vec<Address>) {
+        patient.require_auth();
+
+        if insurers.is_empty() {
+            panic!("At least one insurer required");
+        }
+        for (i, insurer) in insurers.iter().enumerate() {
+            if insurers.first_index_of(&insurer) != Some(i as u32) {
+                panic!("Duplicate insurer in coordination of benefits");
+            }
+            if !Self::is_member(&env, &insurer, &patient) {
+                panic!("Patient not enrolled with insurer");
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Cob(patient.clone()), &insurers);
+
+        env.events()
+            .publish((symbol_short!("cob_set"), patient), insurers);
+    }
+
+    /// Get a patient's insurers in payer order (empty if none recorded)
+    pub fn get_coordination_of_benefits(env: Env, patient: Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Cob(patient))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Whether the patient holds an enrollment with the insurer, as subscriber or dependent
+    fn is_member(env: &Env, insurer_wallet: &Address, patient: &Address) -> bool {
+        let storage = env.storage().persistent();
+        storage.has(&DataKey::Enrollment(
+            insurer_wallet.clone(),
+            patient.clone(),
+        )) || storage.has(&DataKey::Dependent(insurer_wallet.clone(), patient.clone()))
+    }
+
+    // =====================================================
+    //                PRIOR AUTHORIZATION
+    // =====================================================
+
+    /// File a prior authorization request for a member
+    ///
+    /// # Arguments
+    /// * `provider` - The requesting provider
+    /// * `insurer_wallet` - The member's insurer
+    /// * `patient` - The member (subscriber or dependent)
+    /// * `service_code` - The service requiring authorization
+    /// * `requested_units` - Number of units (visits, doses, studies) requested
+    /// * `clinical_hash` - Hash of the supporting clinical documentation
+    ///
+    /// # Returns
+    /// The id of the new authorization request
+    pub fn request_prior_auth(
+        env: Env,
+        provider: Address,
+        insurer_wallet: Address,
+        patient: Address,
+        service_code: String,
+        requested_units: u32,
+        clinical_hash: BytesN<32>,
+    ) -> u64 {
+        provider.require_auth();
+
+        if requested_units == 0 {
+            panic!("Invalid unit count");
+        }
+
+        let now = env.ledger().timestamp();
+        let eligibility = Self::check_eligibility(
+            env.clone(),
+            insurer_wallet.clone(),
+            patient.clone(),
+            service_code.clone(),
+            now,
+        );
+        if eligibility.status != CoverageStatus::Eligible {
+            panic!("Member not eligible for service");
+        }
+
+        let auth_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PriorAuthCount)
+            .unwrap_or(0)
+            + 1;
+
+        let auth = PriorAuthorization {
+            auth_id,
+            insurer: insurer_wallet.clone(),
+            provider,
+            patient: patient.clone(),
+            service_code,
+            requested_units,
+            clinical_hash,
+            status: PriorAuthStatus::Requested,
+            approved_units: 0,
+            used_units: 0,
+            valid_from: 0,
+            valid_to: 0,
+            reason_code: String::from_str(&env, ""),
+            requested_at: now,
+            decided_at: 0,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriorAuth(auth_id), &auth);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriorAuthCount, &auth_id);
+
+        let patient_key = DataKey::PatientPriorAuths(patient);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&patient_key)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(auth_id);
+        env.storage().persistent().set(&patient_key, &ids);
+
+        env.events().publish(
+            (symbol_short!("pa_req"), insurer_wallet, auth_id),
+            symbol_short!("success"),
+        );
+
+        auth_id
+    }
+
+    /// Approve a prior authorization for a number of units within a validity window
+    ///
+    /// # Arguments
+    /// * `reviewer` - An authorized claims reviewer of the insurer
+    /// * `auth_id` - The authorization request
+    /// * `approved_units` - Number of units approved, at most the number requested
+    /// * `valid_from` - Start of the validity window
+    /// * `valid_to` - End of the validity window (exclusive)
+    /// * `reason_code` - Decision reason code
+    pub fn approve_prior_auth(
+        env: Env,
+        reviewer: Address,
+        auth_id: u64,
+        approved_units: u32,
+        valid_from: u64,
+        valid_to: u64,
+        reason_code: String,
+    ) {
+        if approved_units == 0 {
+            panic!("Invalid unit count");
+        }
+        if valid_to <= valid_from {
+            panic!("Invalid validity window");
+        }
+
+        let mut auth = Self::get_prior_auth(env.clone(), auth_id);
+        if approved_units > auth.requested_units {
+            panic!("Approved units exceed requested units");
+        }
+        auth.approved_units = approved_units;
+        auth.valid_from = valid_from;
+        auth.valid_to = valid_to;
+        Self::decide_prior_auth(&env, reviewer, auth, PriorAuthStatus::Approved, reason_code);
+    }
+
+    /// Deny a prior authorization request
+    pub fn deny_prior_auth(env: Env, reviewer: Address, auth_id: u64, reason_code: String) {
+        let auth = Self::get_prior_auth(env.clone(), auth_id);
+        Self::decide_prior_auth(&env, reviewer, auth, PriorAuthStatus::Denied, reason_code);
+    }
+
+    /// Pend a prior authorization request for further review
+    pub fn pend_prior_auth(env: Env, reviewer: Address, auth_id: u64, reason_code: String) {
+        let auth = Self::get_prior_auth(env.clone(), auth_id);
+        Self::decide_prior_auth(&env, reviewer, auth, PriorAuthStatus::Pended, reason_code);
+    }
+
+    /// Consume units from an approved authorization when the service is rendered
+    ///
+    /// # Arguments
+    /// * `provider` - The provider that requested the authorization
+    /// * `auth_id` - The authorization being used
+    /// * `units` - Number of units rendered
+    pub fn use_prior_auth_units(env: Env, provider: Address, auth_id: u64, units: u32) {
+        provider.require_auth();
+
+        let mut auth = Self::get_prior_auth(env.clone(), auth_id);
+        if auth.provider != provider {
+            panic!("Not the requesting provider");
+        }
+        if !Self::prior_auth_usable(&auth, env.ledger().timestamp()) {
+            panic!("Authorization not valid");
+        }
+        if units == 0 || auth.used_units + units > auth.approved_units {
+            panic!("Insufficient authorized units");
+        }
+
+        auth.used_units += units;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriorAuth(auth_id), &auth);
+
+        env.events()
+            .publish((symbol_short!("pa_use"), auth.insurer, auth_id), units);
+    }
+
+    /// Retrieve a prior authorization by id
+    ///
+    /// # Panics
+    /// Panics if the authorization is not found
+    pub fn get_prior_auth(env: Env, auth_id: u64) -> PriorAuthorization {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PriorAuth(auth_id))
+            .expect("Prior authorization not found")
+    }
+
+    /// Get the ids of all prior authorizations filed for a patient
+    pub fn get_patient_prior_auths(env: Env, patient: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PatientPriorAuths(patient))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check whether a patient holds an approved, unexpired authorization with
+    /// units remaining for a service. Intended for other contracts (imaging
+    /// orders, prescriptions) to call before a service proceeds.
+    pub fn has_valid_prior_auth(
+        env: Env,
+        insurer_wallet: Address,
+        patient: Address,
+        service_code: String,
+    ) -> bool {
+        let now = env.ledger().timestamp();
+        for auth_id in Self::get_patient_prior_auths(env.clone(), patient).iter() {
+            let auth = Self::get_prior_auth(env.clone(), auth_id);
+            if auth.insurer == insurer_wallet
+                && auth.service_code == service_code
+                && Self::prior_auth_usable(&auth, now)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn prior_auth_usable(auth: &PriorAuthorization, now: u64) -> bool {
+        auth.status == PriorAuthStatus::Approved
+            && now >= auth.valid_from
+            && now < auth.valid_to
+            && auth.used_units < auth.approved_units
+    }
+
+    fn decide_prior_auth(
+        env: &Env,
+        reviewer: Address,
+        mut auth: PriorAuthorization,
+        status: PriorAuthStatus,
+        reason_code: String,
+    ) {
+        Self::require_reviewer(env, &auth.insurer, &reviewer, &reason_code);
+        if auth.status != PriorAuthStatus::Requested && auth.status != PriorAuthStatus::Pended {
+            panic!("Prior authorization already decided");
+        }
+
+        auth.status = status;
+        auth.reason_code = reason_code;
+        auth.decided_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::PriorAuth(auth.auth_id), &auth);
+
+        env.events().publish(
+            (symbol_short!("pa_upd"), auth.insurer, auth.auth_id),
+            status,
+        );
+    }
+
+    // =====================================================
+    //             LICENSE REGULATION
+    // =====================================================
+
+    /// Set the admin who manages regulators when the contract is deployed,
+    /// so that no later caller can claim the admin role
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address for the contract
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        env.events()
+            .publish((symbol_short!("init"), admin), symbol_short!("success"));
+    }
+
+    /// Grant the regulator role to an address (admin only)
+    pub fn add_regulator(env: Env, admin: Address, regulator: Address) {
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Regulator(regulator.clone()), &true);
+
+        env.events().publish(
+            (symbol_short!("add_reg"), regulator),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Revoke the regulator role from an address (admin only)
+    pub fn remove_regulator(env: Env, admin: Address, regulator: Address) {
+        Self::require_admin(&env, &admin);
+
+        let key = DataKey::Regulator(regulator.clone());
+        if !env.storage().persistent().has(&key) {
+            panic!("Regulator not found");
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("rm_reg"), regulator),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Check whether an address holds the regulator role
+    pub fn is_regulator(env: Env, regulator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Regulator(regulator))
+    }
+
+    /// Verify an insurer's `license_id` and record the license expiry
+    ///
+    /// # Arguments
+    /// * `regulator` - The regulator verifying the license
+    /// * `wallet` - The wallet address of the insurance company
+    /// * `license_expiry` - License expiration timestamp (0 if none)
+    pub fn verify_license(env: Env, regulator: Address, wallet: Address, license_expiry: u64) {
+        let mut insurer = Self::get_insurer_for_regulator(&env, &regulator, &wallet);
+        if insurer.status == InsurerStatus::Suspended {
+            panic!("Insurer is suspended");
+        }
+
+        insurer.status = InsurerStatus::Verified;
+        insurer.license_expiry = license_expiry;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Insurer(wallet.clone()), &insurer);
+
+        env.events()
+            .publish((symbol_short!("lic_ver"), wallet), license_expiry);
+    }
+
+    /// Update the expiry of an insurer's license
+    pub fn set_license_expiry(env: Env, regulator: Address, wallet: Address, license_expiry: u64) {
+        let mut insurer = Self::get_insurer_for_regulator(&env, &regulator, &wallet);
+
+        insurer.license_expiry = license_expiry;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Insurer(wallet.clone()), &insurer);
+
+        env.events()
+            .publish((symbol_short!("lic_exp"), wallet), license_expiry);
+    }
+
+    /// Suspend an insurer. Suspended insurers cannot add claims reviewers or
+    /// decide claims and prior authorizations. The insurer's current status is
+    /// kept so that `reinstate_insurer` can restore it.
+    ///
+    /// # Arguments
+    /// * `regulator` - The regulator suspending the insurer
+    /// * `wallet` - The wallet address of the insurance company
+    /// * `reason` - Reason for the suspension
+    pub fn suspend_insurer(env: Env, regulator: Address, wallet: Address, reason: String) {
+        let mut insurer = Self::get_insurer_for_regulator(&env, &regulator, &wallet);
+        if insurer.status == InsurerStatus::Suspended {
+            panic!("Insurer is suspended");
+        }
+
+        env.storage().persistent().set(
+            &DataKey::StatusBeforeSuspension(wallet.clone()),
+            &insurer.status,
+        );
+        insurer.status = InsurerStatus::Suspended;
+        insurer.status_reason = reason.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Insurer(wallet.clone()), &insurer);
+
+        env.events()
+            .publish((symbol_short!("ins_susp"), wallet), reason);
+    }
+
+    /// Reinstate a suspended insurer, restoring the status it held before
+    /// the suspension
+    ///
+    /// # Arguments
+    /// * `regulator` - The regulator reinstating the insurer
+    /// * `wallet` - The wallet address of the insurance company
+    /// * `reason` - Reason for the reinstatement
+    pub fn reinstate_insurer(env: Env, regulator: Address, wallet: Address, reason: String) {
+        let mut insurer = Self::get_insurer_for_regulator(&env, &regulator, &wallet);
+        if insurer.status != InsurerStatus::Suspended {
+            panic!("Insurer is not suspended");
+        }
+
+        let status_key = DataKey::StatusBeforeSuspension(wallet.clone());
+        insurer.status = env
+            .storage()
+            .persistent()
+            .get(&status_key)
+            .unwrap_or(InsurerStatus::PendingVerification);
+        env.storage().persistent().remove(&status_key);
+        insurer.status_reason = reason.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Insurer(wallet.clone()), &insurer);
+
+        env.events()
+            .publish((symbol_short!("ins_rein"), wallet), reason);
+    }
+
+    /// Check whether an insurer is in good standing: registered, verified
+    /// and holding an unexpired license
+    pub fn is_insurer_active(env: Env, wallet: Address) -> bool {
+        let insurer: Option<InsurerData> =
+            env.storage().persistent().get(&DataKey::Insurer(wallet));
+        match insurer {
+            Some(insurer) => {
+                insurer.status == InsurerStatus::Verified
+                    && (insurer.license_expiry == 0
+                        || insurer.license_expiry > env.ledger().timestamp())
+            }
+            None => false,
+        }
+    }
+
+    fn assert_insurer_active(env: &Env, wallet: &Address) {
+        let insurer = Self::get_insurer(env.clone(), wallet.clone());
+        if insurer.status == InsurerStatus::Suspended {
+            panic!("Insurer is suspended");
+        }
+        if insurer.status != InsurerStatus::Verified {
+            panic!("Insurer is not verified");
+        }
+        if insurer.license_expiry != 0 && insurer.license_expiry <= env.ledger().timestamp() {
+            panic!("Insurer license expired");
+        }
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *admin != stored_admin {
+            panic!("Only admin can perform this action");
+        }
+    }
+
+    fn get_insurer_for_regulator(env: &Env, regulator: &Address, wallet: &Address) -> InsurerData {
+        regulator.require_auth();
+
+        if !Self::is_regulator(env.clone(), regulator.clone()) {
+            panic!("Only a regulator can perform this action");
+        }
+        Self::get_insurer(env.clone(), wallet.clone())
     }
 }
 
@@ -1,12 +1,15 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, BytesN, Env, String, Vec,
+};
 
 #[test]
 fn test_register_insurer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -30,7 +33,7 @@ fn test_register_insurer() {
 #[should_panic(expected = "Insurer already registered")]
 fn test_duplicate_registration() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -48,7 +51,7 @@ fn test_duplicate_registration() {
 #[test]
 fn test_update_insurer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -73,7 +76,7 @@ fn test_update_insurer() {
 #[should_panic(expected = "Insurer not found")]
 fn test_update_nonexistent_insurer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -89,7 +92,7 @@ fn test_update_nonexistent_insurer() {
 #[should_panic(expected = "Insurer not found")]
 fn test_get_nonexistent_insurer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -101,7 +104,7 @@ fn test_get_nonexistent_insurer() {
 #[test]
 fn test_update_contact_details() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -124,7 +127,7 @@ fn test_update_contact_details() {
 #[test]
 fn test_update_coverage_policies() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -148,10 +151,25 @@ fn test_update_coverage_policies() {
 //         CLAIMS REVIEWERS TESTS
 // =====================================================
 
+/// Sets up a regulator and verifies the insurer's license, as required
+/// before it can manage reviewers or decide claims.
+fn verify_insurer(
+    env: &Env,
+    client: &InsurerRegistryClient,
+    admin: &Address,
+    insurer_wallet: &Address,
+) -> Address {
+    let regulator = Address::generate(env);
+    client.add_regulator(admin, &regulator);
+    client.verify_license(&regulator, insurer_wallet, &0);
+    regulator
+}
+
 #[test]
 fn test_add_claims_reviewer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -163,6 +181,7 @@ fn test_add_claims_reviewer() {
     env.mock_all_auths();
 
     client.register_insurer(&insurer_wallet, &name, &license_id, &metadata);
+    verify_insurer(&env, &client, &admin, &insurer_wallet);
     client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
 
     let reviewers = client.get_claims_reviewers(&insurer_wallet);
@@ -173,7 +192,8 @@ fn test_add_claims_reviewer() {
 #[test]
 fn test_add_multiple_claims_reviewers() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -187,6 +207,7 @@ fn test_add_multiple_claims_reviewers() {
     env.mock_all_auths();
 
     client.register_insurer(&insurer_wallet, &name, &license_id, &metadata);
+    verify_insurer(&env, &client, &admin, &insurer_wallet);
     client.add_claims_reviewer(&insurer_wallet, &reviewer1);
     client.add_claims_reviewer(&insurer_wallet, &reviewer2);
     client.add_claims_reviewer(&insurer_wallet, &reviewer3);
@@ -199,7 +220,8 @@ fn test_add_multiple_claims_reviewers() {
 #[should_panic(expected = "Reviewer already authorized")]
 fn test_add_duplicate_reviewer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -211,6 +233,7 @@ fn test_add_duplicate_reviewer() {
     env.mock_all_auths();
 
     client.register_insurer(&insurer_wallet, &name, &license_id, &metadata);
+    verify_insurer(&env, &client, &admin, &insurer_wallet);
     client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
     // Attempt to add same reviewer again - should panic
     client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
@@ -220,7 +243,7 @@ fn test_add_duplicate_reviewer() {
 #[should_panic(expected = "Insurer not registered")]
 fn test_add_reviewer_to_nonexistent_insurer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -235,7 +258,8 @@ fn test_add_reviewer_to_nonexistent_insurer() {
 #[test]
 fn test_remove_claims_reviewer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -247,6 +271,7 @@ fn test_remove_claims_reviewer() {
     env.mock_all_auths();
 
     client.register_insurer(&insurer_wallet, &name, &license_id, &metadata);
+    verify_insurer(&env, &client, &admin, &insurer_wallet);
     client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
 
     let reviewers = client.get_claims_reviewers(&insurer_wallet);
@@ -263,7 +288,7 @@ fn test_remove_claims_reviewer() {
 #[should_panic(expected = "Reviewer not found")]
 fn test_remove_nonexistent_reviewer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -283,7 +308,8 @@ fn test_remove_nonexistent_reviewer() {
 #[test]
 fn test_is_authorized_reviewer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -296,6 +322,7 @@ fn test_is_authorized_reviewer() {
     env.mock_all_auths();
 
     client.register_insurer(&insurer_wallet, &name, &license_id, &metadata);
+    verify_insurer(&env, &client, &admin, &insurer_wallet);
     client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
 
     // Check authorized reviewer
@@ -308,7 +335,7 @@ fn test_is_authorized_reviewer() {
 #[test]
 fn test_get_claims_reviewers_empty() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -327,7 +354,8 @@ fn test_get_claims_reviewers_empty() {
 #[test]
 fn test_full_workflow() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, InsurerRegistry);
+    let admin = Address::generate(&env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
     let client = InsurerRegistryClient::new(&env, &contract_id);
 
     let insurer_wallet = Address::generate(&env);
@@ -341,6 +369,7 @@ fn test_full_workflow() {
     let license_id = String::from_str(&env, "INS-2026-12345");
     let metadata = String::from_str(&env, "Comprehensive health coverage");
     client.register_insurer(&insurer_wallet, &name, &license_id, &metadata);
+    verify_insurer(&env, &client, &admin, &insurer_wallet);
 
     // Update contact details
     let contact = String::from_str(&env, "555-0123, contact@healthguard.com");
@@ -370,3 +399,1303 @@ fn test_full_workflow() {
     assert_eq!(reviewers.len(), 1);
     assert_eq!(reviewers.get(0).unwrap(), reviewer2);
 }
+
+// =====================================================
+//         PLAN CATALOGUE TESTS
+// =====================================================
+
+fn sample_plan(env: &Env) -> InsurancePlan {
+    InsurancePlan {
+        plan_code: String::from_str(env, "PPO-202"),
+        covered_services: vec![
+            env,
+            String::from_str(env, "99213"),
+            String::from_str(env, "70553"),
+        ],
+        copay: 2_500,
+        coinsurance_bps: 2_000,
+        deductible: 100_000,
+        out_of_pocket_max: 500_000,
+        prior_auth_services: vec![env, String::from_str(env, "70553")],
+        effective_from: 1_000,
+        effective_to: 0,
+    }
+}
+
+#[test]
+fn test_publish_plan_and_get_coverage() {
+    let env = Env::default();
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
+    let client = InsurerRegistryClient::new(&env, &contract_id);
+
+    let insurer_wallet = Address::generate(&env);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(2_000);
+
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(&env, "HealthGuard Insurance"),
+        &String::from_str(&env, "INS-2026-12345"),
+        &String::from_str(&env, "Coverage info"),
+    );
+
+    let plan = sample_plan(&env);
+    client.publish_plan(&insurer_wallet, &plan);
+
+    let plan_code = String::from_str(&env, "PPO-202");
+    assert_eq!(client.get_plan(&insurer_wallet, &plan_code), plan);
+    assert_eq!(
+        client.get_plan_codes(&insurer_wallet),
+        vec![&env, plan_code.clone()]
+    );
+
+    let office_visit = client.get_coverage(
+        &insurer_wallet,
+        &plan_code,
+        &String::from_str(&env, "99213"),
+    );
+    assert!(office_visit.covered);
+    assert!(!office_visit.prior_auth_required);
+    assert_eq!(office_visit.copay, 2_500);
+    assert_eq!(office_visit.coinsurance_bps, 2_000);
+
+    let mri = client.get_coverage(
+        &insurer_wallet,
+        &plan_code,
+        &String::from_str(&env, "70553"),
+    );
+    assert!(mri.covered);
+    assert!(mri.prior_auth_required);
+
+    let uncovered = client.get_coverage(
+        &insurer_wallet,
+        &plan_code,
+        &String::from_str(&env, "00000"),
+    );
+    assert!(!uncovered.covered);
+
+    // Republishing with the same code replaces the plan without duplicating the index
+    let mut updated = plan.clone();
+    updated.effective_to = 3_000;
+    client.publish_plan(&insurer_wallet, &updated);
+    assert_eq!(client.get_plan_codes(&insurer_wallet).len(), 1);
+
+    env.ledger().set_timestamp(3_000);
+    let expired = client.get_coverage(
+        &insurer_wallet,
+        &plan_code,
+        &String::from_str(&env, "99213"),
+    );
+    assert!(!expired.covered);
+}
+
+#[test]
+#[should_panic(expected = "Invalid coinsurance")]
+fn test_publish_plan_invalid_coinsurance() {
+    let env = Env::default();
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
+    let client = InsurerRegistryClient::new(&env, &contract_id);
+
+    let insurer_wallet = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(&env, "HealthGuard Insurance"),
+        &String::from_str(&env, "INS-2026-12345"),
+        &String::from_str(&env, "Coverage info"),
+    );
+
+    let mut plan = sample_plan(&env);
+    plan.coinsurance_bps = 10_001;
+    client.publish_plan(&insurer_wallet, &plan);
+}
+
+#[test]
+#[should_panic(expected = "Insurer not registered")]
+fn test_publish_plan_unregistered_insurer() {
+    let env = Env::default();
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
+    let client = InsurerRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    client.publish_plan(&Address::generate(&env), &sample_plan(&env));
+}
+
+// =====================================================
+//         CLAIMS ADJUDICATION TESTS
+// =====================================================
+
+fn setup_claims(env: &Env) -> (InsurerRegistryClient<'_>, Address, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
+    let client = InsurerRegistryClient::new(env, &contract_id);
+
+    let insurer_wallet = Address::generate(env);
+    let reviewer_wallet = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(env, "HealthGuard Insurance"),
+        &String::from_str(env, "INS-2026-12345"),
+        &String::from_str(env, "Coverage info"),
+    );
+    verify_insurer(env, &client, &admin, &insurer_wallet);
+    client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
+
+    (client, insurer_wallet, reviewer_wallet)
+}
+
+fn eob(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7; 32])
+}
+
+fn sample_lines(env: &Env) -> Vec<ClaimLine> {
+    vec![
+        env,
+        ClaimLine {
+            service_code: String::from_str(env, "99213"),
+            amount: 15_000,
+        },
+        ClaimLine {
+            service_code: String::from_str(env, "80053"),
+            amount: 5_000,
+        },
+    ]
+}
+
+#[test]
+fn test_submit_and_approve_claim() {
+    let env = Env::default();
+    let (client, insurer_wallet, reviewer_wallet) = setup_claims(&env);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let docs = vec![&env, BytesN::from_array(&env, &[1; 32])];
+
+    let claim_id = client.submit_claim(
+        &provider,
+        &insurer_wallet,
+        &patient,
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &docs,
+    );
+    assert_eq!(claim_id, 1);
+
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Submitted);
+    assert_eq!(claim.total_amount, 20_000);
+
+    client.approve_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Approved);
+    assert_eq!(claim.approved_amount, 20_000);
+
+    let history = client.get_claim_history(&claim_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().actor, reviewer_wallet);
+    assert_eq!(
+        history.get(1).unwrap().reason_code,
+        String::from_str(&env, "A1")
+    );
+
+    assert_eq!(client.get_provider_claims(&provider), vec![&env, claim_id]);
+    assert_eq!(client.get_patient_claims(&patient), vec![&env, claim_id]);
+}
+
+#[test]
+fn test_claim_info_request_and_partial_approval() {
+    let env = Env::default();
+    let (client, insurer_wallet, reviewer_wallet) = setup_claims(&env);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let claim_id = client.submit_claim(
+        &provider,
+        &insurer_wallet,
+        &patient,
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    client.request_claim_info(&reviewer_wallet, &claim_id, &String::from_str(&env, "M127"));
+    assert_eq!(
+        client.get_claim(&claim_id).status,
+        ClaimStatus::InfoRequested
+    );
+
+    client.respond_to_info_request(
+        &provider,
+        &claim_id,
+        &vec![&env, BytesN::from_array(&env, &[2; 32])],
+    );
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Submitted);
+    assert_eq!(claim.document_hashes.len(), 1);
+
+    client.partially_approve_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &12_000,
+        &String::from_str(&env, "CO-45"),
+        &eob(&env),
+    );
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::PartiallyApproved);
+    assert_eq!(claim.approved_amount, 12_000);
+    assert_eq!(client.get_claim_history(&claim_id).len(), 4);
+}
+
+#[test]
+#[should_panic(expected = "Reviewer not authorized")]
+fn test_unauthorized_reviewer_cannot_adjudicate() {
+    let env = Env::default();
+    let (client, insurer_wallet, _reviewer_wallet) = setup_claims(&env);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    client.deny_claim(
+        &Address::generate(&env),
+        &claim_id,
+        &String::from_str(&env, "CO-50"),
+        &eob(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Claim is not pending review")]
+fn test_cannot_adjudicate_decided_claim() {
+    let env = Env::default();
+    let (client, insurer_wallet, reviewer_wallet) = setup_claims(&env);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    client.deny_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &String::from_str(&env, "CO-50"),
+        &eob(&env),
+    );
+    client.approve_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Reason code required")]
+fn test_adjudication_requires_reason_code() {
+    let env = Env::default();
+    let (client, insurer_wallet, reviewer_wallet) = setup_claims(&env);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    client.approve_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &String::from_str(&env, ""),
+        &eob(&env),
+    );
+}
+
+// =====================================================
+//         ENROLLMENT & ELIGIBILITY TESTS
+// =====================================================
+
+fn setup_enrollment(env: &Env) -> (InsurerRegistryClient<'_>, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
+    let client = InsurerRegistryClient::new(env, &contract_id);
+
+    let insurer_wallet = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(env, "HealthGuard Insurance"),
+        &String::from_str(env, "INS-2026-12345"),
+        &String::from_str(env, "Coverage info"),
+    );
+    verify_insurer(env, &client, &admin, &insurer_wallet);
+    client.publish_plan(&insurer_wallet, &sample_plan(env));
+
+    (client, insurer_wallet)
+}
+
+#[test]
+fn test_enroll_member_and_check_eligibility() {
+    let env = Env::default();
+    let (client, insurer_wallet) = setup_enrollment(&env);
+
+    let subscriber = Address::generate(&env);
+    let dependent = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let plan_code = String::from_str(&env, "PPO-202");
+    let office_visit = String::from_str(&env, "99213");
+
+    client.enroll_member(
+        &insurer_wallet,
+        &subscriber,
+        &String::from_str(&env, "M-0001"),
+        &plan_code,
+        &1_000,
+        &10_000,
+        &vec![&env, dependent.clone()],
+    );
+
+    let enrollment = client.get_enrollment(&insurer_wallet, &subscriber);
+    assert_eq!(enrollment.member_id, String::from_str(&env, "M-0001"));
+    assert!(enrollment.active);
+
+    let result = client.check_eligibility(&insurer_wallet, &subscriber, &office_visit, &5_000);
+    assert_eq!(result.status, CoverageStatus::Eligible);
+    assert_eq!(result.plan_code, plan_code);
+    assert_eq!(result.coverage.copay, 2_500);
+
+    // Dependents resolve to the subscriber's enrollment
+    let result = client.check_eligibility(&insurer_wallet, &dependent, &office_visit, &5_000);
+    assert_eq!(result.status, CoverageStatus::Eligible);
+    assert_eq!(result.member_id, String::from_str(&env, "M-0001"));
+
+    let result = client.check_eligibility(
+        &insurer_wallet,
+        &subscriber,
+        &String::from_str(&env, "00000"),
+        &5_000,
+    );
+    assert_eq!(result.status, CoverageStatus::ServiceNotCovered);
+
+    let result = client.check_eligibility(&insurer_wallet, &subscriber, &office_visit, &10_000);
+    assert_eq!(result.status, CoverageStatus::CoverageInactive);
+
+    let result = client.check_eligibility(&insurer_wallet, &stranger, &office_visit, &5_000);
+    assert_eq!(result.status, CoverageStatus::NotEnrolled);
+}
+
+#[test]
+fn test_update_and_terminate_enrollment() {
+    let env = Env::default();
+    let (client, insurer_wallet) = setup_enrollment(&env);
+
+    let subscriber = Address::generate(&env);
+    let dependent = Address::generate(&env);
+    let new_dependent = Address::generate(&env);
+    let office_visit = String::from_str(&env, "99213");
+
+    client.enroll_member(
+        &insurer_wallet,
+        &subscriber,
+        &String::from_str(&env, "M-0002"),
+        &String::from_str(&env, "PPO-202"),
+        &1_000,
+        &0,
+        &vec![&env, dependent.clone()],
+    );
+
+    client.update_dependents(
+        &insurer_wallet,
+        &subscriber,
+        &vec![&env, new_dependent.clone()],
+    );
+    assert_eq!(
+        client
+            .check_eligibility(&insurer_wallet, &dependent, &office_visit, &5_000)
+            .status,
+        CoverageStatus::NotEnrolled
+    );
+    assert_eq!(
+        client
+            .check_eligibility(&insurer_wallet, &new_dependent, &office_visit, &5_000)
+            .status,
+        CoverageStatus::Eligible
+    );
+
+    client.terminate_enrollment(&insurer_wallet, &subscriber, &6_000);
+    let enrollment = client.get_enrollment(&insurer_wallet, &subscriber);
+    assert!(!enrollment.active);
+    assert_eq!(enrollment.coverage_end, 6_000);
+
+    // Dates inside the past coverage period stay eligible, for dependents too
+    for (member, date, status) in [
+        (&subscriber, 5_000, CoverageStatus::Eligible),
+        (&subscriber, 6_000, CoverageStatus::CoverageInactive),
+        (&new_dependent, 5_000, CoverageStatus::Eligible),
+        (&new_dependent, 6_000, CoverageStatus::CoverageInactive),
+    ] {
+        assert_eq!(
+            client
+                .check_eligibility(&insurer_wallet, member, &office_visit, &date)
+                .status,
+            status
+        );
+    }
+
+    // The dependent can then be covered under another subscriber
+    client.enroll_member(
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "M-0003"),
+        &String::from_str(&env, "PPO-202"),
+        &6_000,
+        &0,
+        &vec![&env, new_dependent.clone()],
+    );
+    let result = client.check_eligibility(&insurer_wallet, &new_dependent, &office_visit, &7_000);
+    assert_eq!(result.status, CoverageStatus::Eligible);
+    assert_eq!(result.member_id, String::from_str(&env, "M-0003"));
+}
+
+#[test]
+fn test_own_enrollment_checked_before_dependent_link() {
+    let env = Env::default();
+    let (client, insurer_wallet) = setup_enrollment(&env);
+
+    let parent = Address::generate(&env);
+    let child = Address::generate(&env);
+    let office_visit = String::from_str(&env, "99213");
+
+    client.enroll_member(
+        &insurer_wallet,
+        &parent,
+        &String::from_str(&env, "M-0010"),
+        &String::from_str(&env, "PPO-202"),
+        &1_000,
+        &3_000,
+        &vec![&env, child.clone()],
+    );
+    client.enroll_member(
+        &insurer_wallet,
+        &child,
+        &String::from_str(&env, "M-0011"),
+        &String::from_str(&env, "PPO-202"),
+        &1_000,
+        &0,
+        &Vec::new(&env),
+    );
+
+    // The parent's coverage has ended, but the child's own has not
+    let result = client.check_eligibility(&insurer_wallet, &child, &office_visit, &5_000);
+    assert_eq!(result.status, CoverageStatus::Eligible);
+    assert_eq!(result.member_id, String::from_str(&env, "M-0011"));
+}
+
+#[test]
+#[should_panic(expected = "Invalid coverage period")]
+fn test_terminate_before_coverage_start() {
+    let env = Env::default();
+    let (client, insurer_wallet) = setup_enrollment(&env);
+
+    let subscriber = Address::generate(&env);
+    client.enroll_member(
+        &insurer_wallet,
+        &subscriber,
+        &String::from_str(&env, "M-0004"),
+        &String::from_str(&env, "PPO-202"),
+        &1_000,
+        &0,
+        &Vec::new(&env),
+    );
+    client.terminate_enrollment(&insurer_wallet, &subscriber, &500);
+}
+
+#[test]
+#[should_panic(expected = "Member already enrolled")]
+fn test_duplicate_enrollment() {
+    let env = Env::default();
+    let (client, insurer_wallet) = setup_enrollment(&env);
+
+    let subscriber = Address::generate(&env);
+    for _ in 0..2 {
+        client.enroll_member(
+            &insurer_wallet,
+            &subscriber,
+            &String::from_str(&env, "M-0003"),
+            &String::from_str(&env, "PPO-202"),
+            &1_000,
+            &0,
+            &Vec::new(&env),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "Plan not found")]
+fn test_enroll_member_unknown_plan() {
+    let env = Env::default();
+    let (client, insurer_wallet) = setup_enrollment(&env);
+
+    client.enroll_member(
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "M-0004"),
+        &String::from_str(&env, "HMO-999"),
+        &1_000,
+        &0,
+        &Vec::new(&env),
+    );
+}
+
+// =====================================================
+//         PRIOR AUTHORIZATION TESTS
+// =====================================================
+
+fn setup_prior_auth(env: &Env) -> (InsurerRegistryClient<'_>, Address, Address, Address) {
+    let (client, insurer_wallet) = setup_enrollment(env);
+
+    let reviewer_wallet = Address::generate(env);
+    let patient = Address::generate(env);
+
+    client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
+    client.enroll_member(
+        &insurer_wallet,
+        &patient,
+        &String::from_str(env, "M-0100"),
+        &String::from_str(env, "PPO-202"),
+        &0,
+        &0,
+        &Vec::new(env),
+    );
+
+    (client, insurer_wallet, reviewer_wallet, patient)
+}
+
+#[test]
+fn test_prior_auth_approval_and_usage() {
+    let env = Env::default();
+    env.ledger().set_timestamp(2_000);
+    let (client, insurer_wallet, reviewer_wallet, patient) = setup_prior_auth(&env);
+
+    let provider = Address::generate(&env);
+    let mri = String::from_str(&env, "70553");
+
+    let auth_id = client.request_prior_auth(
+        &provider,
+        &insurer_wallet,
+        &patient,
+        &mri,
+        &2,
+        &BytesN::from_array(&env, &[5; 32]),
+    );
+    assert!(!client.has_valid_prior_auth(&insurer_wallet, &patient, &mri));
+
+    client.pend_prior_auth(&reviewer_wallet, &auth_id, &String::from_str(&env, "PEND"));
+    assert_eq!(
+        client.get_prior_auth(&auth_id).status,
+        PriorAuthStatus::Pended
+    );
+
+    client.approve_prior_auth(
+        &reviewer_wallet,
+        &auth_id,
+        &2,
+        &2_000,
+        &9_000,
+        &String::from_str(&env, "A1"),
+    );
+    assert!(client.has_valid_prior_auth(&insurer_wallet, &patient, &mri));
+    assert_eq!(
+        client.get_patient_prior_auths(&patient),
+        vec![&env, auth_id]
+    );
+
+    client.use_prior_auth_units(&provider, &auth_id, &2);
+    assert_eq!(client.get_prior_auth(&auth_id).used_units, 2);
+    assert!(!client.has_valid_prior_auth(&insurer_wallet, &patient, &mri));
+}
+
+#[test]
+fn test_prior_auth_expires() {
+    let env = Env::default();
+    env.ledger().set_timestamp(2_000);
+    let (client, insurer_wallet, reviewer_wallet, patient) = setup_prior_auth(&env);
+
+    let mri = String::from_str(&env, "70553");
+    let auth_id = client.request_prior_auth(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &patient,
+        &mri,
+        &1,
+        &BytesN::from_array(&env, &[5; 32]),
+    );
+    client.approve_prior_auth(
+        &reviewer_wallet,
+        &auth_id,
+        &1,
+        &2_000,
+        &3_000,
+        &String::from_str(&env, "A1"),
+    );
+    assert!(client.has_valid_prior_auth(&insurer_wallet, &patient, &mri));
+
+    env.ledger().set_timestamp(3_000);
+    assert!(!client.has_valid_prior_auth(&insurer_wallet, &patient, &mri));
+}
+
+#[test]
+#[should_panic(expected = "Approved units exceed requested units")]
+fn test_prior_auth_approval_capped_at_requested_units() {
+    let env = Env::default();
+    env.ledger().set_timestamp(2_000);
+    let (client, insurer_wallet, reviewer_wallet, patient) = setup_prior_auth(&env);
+
+    let auth_id = client.request_prior_auth(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &patient,
+        &String::from_str(&env, "70553"),
+        &2,
+        &BytesN::from_array(&env, &[5; 32]),
+    );
+    client.approve_prior_auth(
+        &reviewer_wallet,
+        &auth_id,
+        &3,
+        &2_000,
+        &3_000,
+        &String::from_str(&env, "A1"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Prior authorization already decided")]
+fn test_prior_auth_denied_is_final() {
+    let env = Env::default();
+    env.ledger().set_timestamp(2_000);
+    let (client, insurer_wallet, reviewer_wallet, patient) = setup_prior_auth(&env);
+
+    let auth_id = client.request_prior_auth(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &patient,
+        &String::from_str(&env, "70553"),
+        &1,
+        &BytesN::from_array(&env, &[5; 32]),
+    );
+    client.deny_prior_auth(&reviewer_wallet, &auth_id, &String::from_str(&env, "N1"));
+    client.approve_prior_auth(
+        &reviewer_wallet,
+        &auth_id,
+        &1,
+        &0,
+        &1_000,
+        &String::from_str(&env, "A1"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Member not eligible for service")]
+fn test_prior_auth_requires_eligible_member() {
+    let env = Env::default();
+    let (client, insurer_wallet, _reviewer_wallet, _patient) = setup_prior_auth(&env);
+
+    client.request_prior_auth(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "70553"),
+        &1,
+        &BytesN::from_array(&env, &[5; 32]),
+    );
+}
+
+// =====================================================
+//         LICENSE REGULATION TESTS
+// =====================================================
+
+fn setup_regulation(env: &Env) -> (InsurerRegistryClient<'_>, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
+    let client = InsurerRegistryClient::new(env, &contract_id);
+
+    let regulator = Address::generate(env);
+    let insurer_wallet = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.add_regulator(&admin, &regulator);
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(env, "HealthGuard Insurance"),
+        &String::from_str(env, "INS-2026-12345"),
+        &String::from_str(env, "Coverage info"),
+    );
+
+    (client, admin, regulator, insurer_wallet)
+}
+
+#[test]
+fn test_verify_suspend_and_reinstate_insurer() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1_000);
+    let (client, _admin, regulator, insurer_wallet) = setup_regulation(&env);
+
+    assert_eq!(
+        client.get_insurer(&insurer_wallet).status,
+        InsurerStatus::PendingVerification
+    );
+
+    client.verify_license(&regulator, &insurer_wallet, &50_000);
+    let insurer = client.get_insurer(&insurer_wallet);
+    assert_eq!(insurer.status, InsurerStatus::Verified);
+    assert_eq!(insurer.license_expiry, 50_000);
+    assert!(client.is_insurer_active(&insurer_wallet));
+
+    let reason = String::from_str(&env, "Solvency review");
+    client.suspend_insurer(&regulator, &insurer_wallet, &reason);
+    let insurer = client.get_insurer(&insurer_wallet);
+    assert_eq!(insurer.status, InsurerStatus::Suspended);
+    assert_eq!(insurer.status_reason, reason);
+    assert!(!client.is_insurer_active(&insurer_wallet));
+
+    client.reinstate_insurer(
+        &regulator,
+        &insurer_wallet,
+        &String::from_str(&env, "Review passed"),
+    );
+    assert!(client.is_insurer_active(&insurer_wallet));
+
+    client.set_license_expiry(&regulator, &insurer_wallet, &1_000);
+    assert!(!client.is_insurer_active(&insurer_wallet));
+}
+
+#[test]
+#[should_panic(expected = "Insurer is suspended")]
+fn test_suspended_insurer_cannot_add_reviewer() {
+    let env = Env::default();
+    let (client, _admin, regulator, insurer_wallet) = setup_regulation(&env);
+
+    client.suspend_insurer(
+        &regulator,
+        &insurer_wallet,
+        &String::from_str(&env, "License lapsed"),
+    );
+    client.add_claims_reviewer(&insurer_wallet, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Insurer is suspended")]
+fn test_suspended_insurer_cannot_adjudicate() {
+    let env = Env::default();
+    let (client, _admin, regulator, insurer_wallet) = setup_regulation(&env);
+
+    let reviewer_wallet = Address::generate(&env);
+    client.verify_license(&regulator, &insurer_wallet, &0);
+    client.add_claims_reviewer(&insurer_wallet, &reviewer_wallet);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    client.suspend_insurer(
+        &regulator,
+        &insurer_wallet,
+        &String::from_str(&env, "License lapsed"),
+    );
+    client.approve_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Insurer is not verified")]
+fn test_unverified_insurer_cannot_add_reviewer() {
+    let env = Env::default();
+    let (client, _admin, _regulator, insurer_wallet) = setup_regulation(&env);
+
+    assert!(!client.is_insurer_active(&insurer_wallet));
+    client.add_claims_reviewer(&insurer_wallet, &Address::generate(&env));
+}
+
+#[test]
+fn test_reinstate_restores_status_before_suspension() {
+    let env = Env::default();
+    let (client, _admin, regulator, insurer_wallet) = setup_regulation(&env);
+
+    client.suspend_insurer(
+        &regulator,
+        &insurer_wallet,
+        &String::from_str(&env, "Complaint received"),
+    );
+    client.reinstate_insurer(
+        &regulator,
+        &insurer_wallet,
+        &String::from_str(&env, "Complaint withdrawn"),
+    );
+    assert_eq!(
+        client.get_insurer(&insurer_wallet).status,
+        InsurerStatus::PendingVerification
+    );
+    assert!(!client.is_insurer_active(&insurer_wallet));
+}
+
+#[test]
+#[should_panic(expected = "Only a regulator can perform this action")]
+fn test_non_regulator_cannot_suspend() {
+    let env = Env::default();
+    let (client, _admin, _regulator, insurer_wallet) = setup_regulation(&env);
+
+    client.suspend_insurer(
+        &insurer_wallet,
+        &insurer_wallet,
+        &String::from_str(&env, "Self suspension"),
+    );
+}
+
+// =====================================================
+//         REVIEWER TIER TESTS
+// =====================================================
+
+#[test]
+fn test_claim_above_limit_escalates_to_higher_tier() {
+    let env = Env::default();
+    let (client, insurer_wallet, junior) = setup_claims(&env);
+
+    let senior = Address::generate(&env);
+    client.add_claims_reviewer(&insurer_wallet, &senior);
+    client.set_reviewer_tier(&insurer_wallet, &1, &10_000);
+    client.set_reviewer_tier(&insurer_wallet, &2, &100_000);
+    client.set_reviewer_role(&insurer_wallet, &senior, &2, &Vec::new(&env));
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    // 20_000 exceeds the tier 1 limit, so the approval escalates instead
+    client.approve_claim(
+        &junior,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Escalated);
+    assert_eq!(claim.required_tier, 2);
+    assert_eq!(claim.approved_amount, 0);
+
+    client.approve_claim(
+        &senior,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Approved);
+    assert_eq!(claim.approved_amount, 20_000);
+    assert_eq!(client.get_claim_history(&claim_id).len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Claim requires a higher reviewer tier")]
+fn test_escalated_claim_rejects_lower_tier() {
+    let env = Env::default();
+    let (client, insurer_wallet, junior) = setup_claims(&env);
+    client.set_reviewer_tier(&insurer_wallet, &1, &10_000);
+    client.set_reviewer_tier(&insurer_wallet, &2, &100_000);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    client.approve_claim(
+        &junior,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+    client.deny_claim(
+        &junior,
+        &claim_id,
+        &String::from_str(&env, "CO-50"),
+        &eob(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "No higher reviewer tier configured")]
+fn test_cannot_escalate_past_highest_tier() {
+    let env = Env::default();
+    let (client, insurer_wallet, junior) = setup_claims(&env);
+    client.set_reviewer_tier(&insurer_wallet, &1, &10_000);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+
+    // 20_000 exceeds the only configured tier's limit
+    client.approve_claim(
+        &junior,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Claim outside reviewer specialty scope")]
+fn test_reviewer_specialty_scope() {
+    let env = Env::default();
+    let (client, insurer_wallet, reviewer_wallet) = setup_claims(&env);
+
+    client.set_reviewer_role(
+        &insurer_wallet,
+        &reviewer_wallet,
+        &1,
+        &vec![&env, String::from_str(&env, "cardiology")],
+    );
+    let profile = client.get_reviewer_profile(&insurer_wallet, &reviewer_wallet);
+    assert_eq!(profile.specialties.len(), 1);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &insurer_wallet,
+        &Address::generate(&env),
+        &String::from_str(&env, "radiology"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+    client.approve_claim(
+        &reviewer_wallet,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+}
+
+// =====================================================
+//         CONTACT CHANNEL TESTS
+// =====================================================
+
+#[test]
+fn test_typed_contacts() {
+    let env = Env::default();
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
+    let client = InsurerRegistryClient::new(&env, &contract_id);
+
+    let insurer_wallet = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(&env, "HealthGuard Insurance"),
+        &String::from_str(&env, "INS-2026-12345"),
+        &String::from_str(&env, "Coverage info"),
+    );
+
+    let claims_id = client.add_contact(
+        &insurer_wallet,
+        &ContactType::Claims,
+        &ContactChannel::Phone,
+        &BytesN::from_array(&env, &[1; 32]),
+        &String::from_str(&env, "Mon-Fri 08:00-18:00"),
+    );
+    let fraud_id = client.add_contact(
+        &insurer_wallet,
+        &ContactType::FraudReporting,
+        &ContactChannel::Email,
+        &BytesN::from_array(&env, &[2; 32]),
+        &String::from_str(&env, "24/7"),
+    );
+    assert_eq!(claims_id, 1);
+    assert_eq!(fraud_id, 2);
+
+    assert_eq!(client.get_contacts(&insurer_wallet, &None).len(), 2);
+    let fraud = client.get_contacts(&insurer_wallet, &Some(ContactType::FraudReporting));
+    assert_eq!(fraud.len(), 1);
+    assert_eq!(fraud.get(0).unwrap().channel, ContactChannel::Email);
+
+    client.update_contact(
+        &insurer_wallet,
+        &claims_id,
+        &ContactChannel::Portal,
+        &BytesN::from_array(&env, &[3; 32]),
+        &String::from_str(&env, "24/7"),
+    );
+    let claims = client.get_contact(&insurer_wallet, &claims_id);
+    assert_eq!(claims.contact_type, ContactType::Claims);
+    assert_eq!(claims.channel, ContactChannel::Portal);
+
+    client.remove_contact(&insurer_wallet, &fraud_id);
+    assert_eq!(client.get_contacts(&insurer_wallet, &None).len(), 1);
+    assert!(client.has_contact(&insurer_wallet, &claims_id));
+    assert!(!client.has_contact(&insurer_wallet, &fraud_id));
+}
+
+#[test]
+#[should_panic(expected = "Contact not found")]
+fn test_get_removed_contact() {
+    let env = Env::default();
+    let contract_id = env.register(InsurerRegistry, (Address::generate(&env),));
+    let client = InsurerRegistryClient::new(&env, &contract_id);
+
+    let insurer_wallet = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.register_insurer(
+        &insurer_wallet,
+        &String::from_str(&env, "HealthGuard Insurance"),
+        &String::from_str(&env, "INS-2026-12345"),
+        &String::from_str(&env, "Coverage info"),
+    );
+    let contact_id = client.add_contact(
+        &insurer_wallet,
+        &ContactType::MemberServices,
+        &ContactChannel::Phone,
+        &BytesN::from_array(&env, &[1; 32]),
+        &String::from_str(&env, "Mon-Fri"),
+    );
+    client.remove_contact(&insurer_wallet, &contact_id);
+    client.get_contact(&insurer_wallet, &contact_id);
+}
+
+// =====================================================
+//         COORDINATION OF BENEFITS TESTS
+// =====================================================
+
+fn setup_cob(
+    env: &Env,
+) -> (
+    InsurerRegistryClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(InsurerRegistry, (admin.clone(),));
+    let client = InsurerRegistryClient::new(env, &contract_id);
+
+    let primary = Address::generate(env);
+    let secondary = Address::generate(env);
+    let reviewer = Address::generate(env);
+    let patient = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.register_insurer(
+        &primary,
+        &String::from_str(env, "HealthGuard Insurance"),
+        &String::from_str(env, "INS-2026-12345"),
+        &String::from_str(env, "Coverage info"),
+    );
+    client.register_insurer(
+        &secondary,
+        &String::from_str(env, "Second Shield Mutual"),
+        &String::from_str(env, "INS-2026-67890"),
+        &String::from_str(env, "Coverage info"),
+    );
+    let regulator = verify_insurer(env, &client, &admin, &primary);
+    client.verify_license(&regulator, &secondary, &0);
+    client.add_claims_reviewer(&primary, &reviewer);
+    for (insurer, member_id) in [(&primary, "M-0001"), (&secondary, "S-0001")] {
+        client.publish_plan(insurer, &sample_plan(env));
+        client.enroll_member(
+            insurer,
+            &patient,
+            &String::from_str(env, member_id),
+            &String::from_str(env, "PPO-202"),
+            &1_000,
+            &0,
+            &Vec::new(env),
+        );
+    }
+    client.set_coordination_of_benefits(&patient, &vec![env, primary.clone(), secondary.clone()]);
+
+    (client, primary, reviewer, secondary, patient)
+}
+
+#[test]
+fn test_coordination_of_benefits_creates_secondary_claim() {
+    let env = Env::default();
+    let (client, primary, reviewer, secondary, patient) = setup_cob(&env);
+    let provider = Address::generate(&env);
+
+    let payers = client.get_coordination_of_benefits(&patient);
+    assert_eq!(payers, vec![&env, primary.clone(), secondary.clone()]);
+
+    let primary_id = client.submit_claim(
+        &provider,
+        &primary,
+        &patient,
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &vec![&env, BytesN::from_array(&env, &[1; 32])],
+    );
+    client.partially_approve_claim(
+        &reviewer,
+        &primary_id,
+        &14_000,
+        &String::from_str(&env, "CO-45"),
+        &eob(&env),
+    );
+
+    let primary_claim = client.get_claim(&primary_id);
+    assert_eq!(primary_claim.eob_hash, Some(eob(&env)));
+
+    let secondary_id = primary_id + 1;
+    let secondary_claim = client.get_claim(&secondary_id);
+    assert_eq!(secondary_claim.insurer, secondary);
+    assert_eq!(secondary_claim.status, ClaimStatus::Submitted);
+    assert_eq!(secondary_claim.total_amount, 6_000);
+    // Only the unpaid part of each primary line is billed on
+    assert_eq!(secondary_claim.lines.len(), 2);
+    assert_eq!(secondary_claim.lines.get(0).unwrap().amount, 1_000);
+    assert_eq!(secondary_claim.lines.get(1).unwrap().amount, 5_000);
+    assert_eq!(secondary_claim.primary_claim_id, Some(primary_id));
+    assert_eq!(secondary_claim.primary_eob_hash, primary_claim.eob_hash);
+    assert_eq!(secondary_claim.document_hashes.len(), 1);
+    let submitted = client.get_claim_history(&secondary_id).get(0).unwrap();
+    assert_eq!(submitted.status, ClaimStatus::Submitted);
+    assert_eq!(submitted.actor, provider);
+    assert_eq!(
+        client.get_patient_claims(&patient),
+        vec![&env, primary_id, secondary_id]
+    );
+
+    // The last payer in the list leaves any remaining balance with the patient
+    let secondary_reviewer = Address::generate(&env);
+    client.add_claims_reviewer(&secondary, &secondary_reviewer);
+    client.partially_approve_claim(
+        &secondary_reviewer,
+        &secondary_id,
+        &5_000,
+        &String::from_str(&env, "PR-1"),
+        &eob(&env),
+    );
+    assert_eq!(client.get_patient_claims(&patient).len(), 2);
+}
+
+#[test]
+fn test_fully_paid_claim_has_no_secondary() {
+    let env = Env::default();
+    let (client, primary, reviewer, _secondary, patient) = setup_cob(&env);
+
+    let claim_id = client.submit_claim(
+        &Address::generate(&env),
+        &primary,
+        &patient,
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+    client.approve_claim(
+        &reviewer,
+        &claim_id,
+        &String::from_str(&env, "A1"),
+        &eob(&env),
+    );
+
+    assert_eq!(client.get_claim(&claim_id).approved_amount, 20_000);
+    assert_eq!(client.get_patient_claims(&patient).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Patient not enrolled with insurer")]
+fn test_coordination_of_benefits_requires_enrollment() {
+    let env = Env::default();
+    let (client, primary, _reviewer, _secondary, patient) = setup_cob(&env);
+
+    client.set_coordination_of_benefits(&patient, &vec![&env, primary, Address::generate(&env)]);
+}
+
+#[test]
+fn test_info_request_does_not_bill_secondary() {
+    let env = Env::default();
+    let (client, primary, reviewer, _secondary, patient) = setup_cob(&env);
+    let provider = Address::generate(&env);
+
+    let claim_id = client.submit_claim(
+        &provider,
+        &primary,
+        &patient,
+        &String::from_str(&env, "general"),
+        &sample_lines(&env),
+        &Vec::new(&env),
+    );
+    client.request_claim_info(&reviewer, &claim_id, &String::from_str(&env, "M127"));
+    assert_eq!(client.get_patient_claims(&patient).len(), 1);
+    assert_eq!(client.get_claim(&claim_id).eob_hash, None);
+
+    client.respond_to_info_request(
+        &provider,
+        &claim_id,
+        &vec![&env, BytesN::from_array(&env, &[2; 32])],
+    );
+    client.partially_approve_claim(
+        &reviewer,
+        &claim_id,
+        &14_000,
+        &String::from_str(&env, "CO-45"),
+        &eob(&env),
+    );
+
+    let claims = client.get_patient_claims(&patient);
+    assert_eq!(claims.len(), 2);
+    assert_eq!(
+        client.get_claim(&claims.get(1).unwrap()).total_amount,
+        6_000
+    );
+}
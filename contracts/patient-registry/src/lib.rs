@@ -1,7 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Bytes, Env, Map, String, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Bytes, Env, Map,
+    String, Vec,
 };
 
 /// --------------------
@@ -18,6 +19,8 @@ pub struct PatientData {
 /// --------------------
 /// Doctor Structures
 /// --------------------
+/// Doctors are registered in the doctor registry. These records predate it and
+/// are kept only so they can be imported there.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DoctorData {
@@ -27,6 +30,13 @@ pub struct DoctorData {
     pub verified: bool,
 }
 
+/// Doctor registry interface used to resolve doctor identity and verification
+#[contractclient(name = "DoctorRegistryClient")]
+pub trait DoctorRegistryInterface {
+    fn is_active_doctor(env: Env, wallet: Address) -> bool;
+    fn is_verified_doctor(env: Env, wallet: Address) -> bool;
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
@@ -34,9 +44,10 @@ pub struct DoctorData {
 pub enum DataKey {
     Patient(Address),
     Doctor(Address),
-    Institution(Address),
     MedicalRecords(Address),
     AuthorizedDoctors(Address),
+    Admin,
+    DoctorRegistry, // Address of the doctor registry contract
 }
 
 #[contracttype]
@@ -53,6 +64,55 @@ pub struct MedicalRegistry;
 
 #[contractimpl]
 impl MedicalRegistry {
+    // =====================================================
+    //                    ADMIN LOGIC
+    // =====================================================
+
+    /// Sets the admin and doctor registry when the contract is deployed, so
+    /// that no later caller can claim the admin role
+    pub fn __constructor(env: Env, admin: Address, doctor_registry: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::DoctorRegistry, &doctor_registry);
+
+        env.events()
+            .publish((symbol_short!("init"), admin), symbol_short!("success"));
+    }
+
+    /// Points the contract at a new doctor registry, which decides who counts
+    /// as a registered and verified doctor (admin only)
+    pub fn set_doctor_registry(env: Env, admin: Address, doctor_registry: Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        if admin != stored_admin {
+            panic!("Only admin can perform this action");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::DoctorRegistry, &doctor_registry);
+
+        env.events().publish(
+            (symbol_short!("set_dreg"), doctor_registry),
+            symbol_short!("success"),
+        );
+    }
+
+    fn doctor_registry(env: &Env) -> DoctorRegistryClient<'_> {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DoctorRegistry)
+            .expect("Doctor registry not configured");
+        DoctorRegistryClient::new(env, &registry)
+    }
+
     // =====================================================
     //                    PATIENT LOGIC
     // =====================================================
@@ -110,57 +170,14 @@ impl MedicalRegistry {
     //                    DOCTOR LOGIC
     // =====================================================
 
-    pub fn register_doctor(
-        env: Env,
-        wallet: Address,
-        name: String,
-        specialization: String,
-        certificate_hash: Bytes,
-    ) {
-        wallet.require_auth();
-
-        let key = DataKey::Doctor(wallet.clone());
-        if env.storage().persistent().has(&key) {
-            panic!("Doctor already registered");
-        }
-
-        let doctor = DoctorData {
-            name,
-            specialization,
-            certificate_hash,
-            verified: false,
-        };
-
-        env.storage().persistent().set(&key, &doctor);
-
-        env.events()
-            .publish((symbol_short!("reg_doc"), wallet), symbol_short!("success"));
-    }
-
-    pub fn verify_doctor(env: Env, wallet: Address, institution_wallet: Address) {
-        institution_wallet.require_auth();
-
-        let inst_key = DataKey::Institution(institution_wallet);
-        if !env.storage().persistent().has(&inst_key) {
-            panic!("Unauthorized institution");
-        }
-
-        let doc_key = DataKey::Doctor(wallet.clone());
-        let mut doctor: DoctorData = env
-            .storage()
-            .persistent()
-            .get(&doc_key)
-            .expect("Doctor not found");
-
-        doctor.verified = true;
-        env.storage().persistent().set(&doc_key, &doctor);
-
-        env.events().publish(
-            (symbol_short!("ver_doc"), wallet),
-            symbol_short!("verified"),
-        );
+    /// Whether the doctor registry lists the wallet as an active doctor with a
+    /// valid license
+    pub fn is_doctor_verified(env: Env, wallet: Address) -> bool {
+        Self::doctor_registry(&env).is_verified_doctor(&wallet)
     }
 
+    /// Returns a legacy doctor record; kept so the doctor registry can import
+    /// doctors registered here before it became the canonical source
     pub fn get_doctor(env: Env, wallet: Address) -> DoctorData {
         let key = DataKey::Doctor(wallet);
         env.storage()
@@ -169,16 +186,6 @@ impl MedicalRegistry {
             .expect("Doctor not found")
     }
 
-    // =====================================================
-    //              INSTITUTION MANAGEMENT
-    // =====================================================
-
-    pub fn register_institution(env: Env, institution_wallet: Address) {
-        institution_wallet.require_auth();
-        let key = DataKey::Institution(institution_wallet);
-        env.storage().persistent().set(&key, &true);
-    }
-
     // =====================================================
     //            MEDICAL RECORD ACCESS CONTROL
     // =====================================================
@@ -186,6 +193,10 @@ impl MedicalRegistry {
     pub fn grant_access(env: Env, patient: Address, doctor: Address) {
         patient.require_auth();
 
+        if !Self::doctor_registry(&env).is_active_doctor(&doctor) {
+            panic!("Doctor not registered");
+        }
+
         let key = DataKey::AuthorizedDoctors(patient.clone());
         let mut map: Map<Address, bool> = env
             .storage()
@@ -242,6 +253,9 @@ impl MedicalRegistry {
         if !access_map.contains_key(doctor.clone()) {
             panic!("Doctor not authorized");
         }
+        if !Self::is_doctor_verified(env.clone(), doctor.clone()) {
+            panic!("Doctor not verified");
+        }
 
         let record = MedicalRecord {
             doctor,
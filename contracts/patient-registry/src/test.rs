@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, Env, String,
+};
 
 /// ------------------------------------------------
 /// PATIENT TESTS
@@ -10,7 +13,10 @@ use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, String};
 #[test]
 fn test_register_and_get_patient() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
+    let contract_id = env.register(
+        MedicalRegistry,
+        (Address::generate(&env), Address::generate(&env)),
+    );
     let client = MedicalRegistryClient::new(&env, &contract_id);
 
     let patient_wallet = Address::generate(&env);
@@ -31,7 +37,10 @@ fn test_register_and_get_patient() {
 #[test]
 fn test_update_patient() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
+    let contract_id = env.register(
+        MedicalRegistry,
+        (Address::generate(&env), Address::generate(&env)),
+    );
     let client = MedicalRegistryClient::new(&env, &contract_id);
 
     let patient_wallet = Address::generate(&env);
@@ -53,7 +62,10 @@ fn test_update_patient() {
 #[test]
 fn test_is_patient_registered() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
+    let contract_id = env.register(
+        MedicalRegistry,
+        (Address::generate(&env), Address::generate(&env)),
+    );
     let client = MedicalRegistryClient::new(&env, &contract_id);
 
     let patient_wallet = Address::generate(&env);
@@ -76,95 +88,132 @@ fn test_is_patient_registered() {
 }
 
 /// ------------------------------------------------
-/// DOCTOR + INSTITUTION TESTS
+/// DOCTOR TESTS
 /// ------------------------------------------------
 
-#[test]
-fn test_register_and_get_doctor() {
-    let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
-    let client = MedicalRegistryClient::new(&env, &contract_id);
-
-    let doctor_wallet = Address::generate(&env);
-    let name = String::from_str(&env, "Dr. Alice");
-    let specialization = String::from_str(&env, "Cardiology");
-    let cert_hash = Bytes::from_array(&env, &[1, 2, 3, 4]);
+/// Registers a doctor registry with one licensed doctor and points a new
+/// medical registry at it
+fn setup_with_doctor(
+    env: &Env,
+) -> (
+    MedicalRegistryClient<'_>,
+    doctor_registry::DoctorRegistryClient<'_>,
+    Address,
+    Address,
+) {
+    let admin = Address::generate(env);
+    let doctors_id = env.register(doctor_registry::DoctorRegistry, (admin.clone(),));
+    let doctors = doctor_registry::DoctorRegistryClient::new(env, &doctors_id);
+    let contract_id = env.register(MedicalRegistry, (admin.clone(), doctors_id.clone()));
+    let client = MedicalRegistryClient::new(env, &contract_id);
+
+    let issuer = Address::generate(env);
+    let doctor = Address::generate(env);
 
     env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    doctors.add_credential_issuer(&admin, &issuer);
+    doctors.create_doctor_profile(
+        &doctor,
+        &String::from_str(env, "Dr. Alice"),
+        &String::from_str(env, "Cardiology"),
+        &Address::generate(env),
+    );
+    doctors.attest_license(
+        &issuer,
+        &doctor,
+        &String::from_str(env, "A123456"),
+        &String::from_str(env, "US-CA"),
+        &100_000,
+    );
 
-    client.register_doctor(&doctor_wallet, &name, &specialization, &cert_hash);
-
-    let doctor = client.get_doctor(&doctor_wallet);
-    assert_eq!(doctor.name, name);
-    assert_eq!(doctor.specialization, specialization);
-    assert_eq!(doctor.certificate_hash, cert_hash);
-    assert_eq!(doctor.verified, false);
+    (client, doctors, admin, doctor)
 }
 
 #[test]
-fn test_register_institution_and_verify_doctor() {
+fn test_doctor_verification_resolves_through_registry() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
-    let client = MedicalRegistryClient::new(&env, &contract_id);
-
-    let doctor_wallet = Address::generate(&env);
-    let institution_wallet = Address::generate(&env);
-
-    let name = String::from_str(&env, "Dr. Bob");
-    let specialization = String::from_str(&env, "Neurology");
-    let cert_hash = Bytes::from_array(&env, &[9, 9, 9]);
+    let (client, doctors, admin, doctor) = setup_with_doctor(&env);
 
-    env.mock_all_auths();
-
-    // Register doctor
-    client.register_doctor(&doctor_wallet, &name, &specialization, &cert_hash);
+    assert!(client.is_doctor_verified(&doctor));
+    assert!(!client.is_doctor_verified(&Address::generate(&env)));
 
-    // Register institution
-    client.register_institution(&institution_wallet);
+    doctors.suspend_doctor(&admin, &doctor, &String::from_str(&env, "Under review"));
+    assert!(!client.is_doctor_verified(&doctor));
+}
 
-    // Verify doctor
-    client.verify_doctor(&doctor_wallet, &institution_wallet);
+#[test]
+#[should_panic(expected = "Only admin can perform this action")]
+fn test_only_admin_can_set_doctor_registry() {
+    let env = Env::default();
+    let (client, _doctors, _admin, _doctor) = setup_with_doctor(&env);
 
-    let doctor = client.get_doctor(&doctor_wallet);
-    assert_eq!(doctor.verified, true);
+    client.set_doctor_registry(&Address::generate(&env), &Address::generate(&env));
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized institution")]
-fn test_verify_doctor_by_unregistered_institution_should_fail() {
+fn test_get_legacy_doctor() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
+    let contract_id = env.register(
+        MedicalRegistry,
+        (Address::generate(&env), Address::generate(&env)),
+    );
     let client = MedicalRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
-    let fake_institution = Address::generate(&env);
+    let doctor = DoctorData {
+        name: String::from_str(&env, "Dr. Bob"),
+        specialization: String::from_str(&env, "Neurology"),
+        certificate_hash: Bytes::from_array(&env, &[9, 9, 9]),
+        verified: true,
+    };
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Doctor(doctor_wallet.clone()), &doctor);
+    });
+
+    assert_eq!(client.get_doctor(&doctor_wallet), doctor);
+}
 
-    let name = String::from_str(&env, "Dr. Eve");
-    let specialization = String::from_str(&env, "Oncology");
-    let cert_hash = Bytes::from_array(&env, &[7, 7, 7]);
+#[test]
+#[should_panic(expected = "Doctor not registered")]
+fn test_grant_access_to_unregistered_doctor_should_fail() {
+    let env = Env::default();
+    let (client, _doctors, _admin, _doctor) = setup_with_doctor(&env);
 
-    env.mock_all_auths();
+    client.grant_access(&Address::generate(&env), &Address::generate(&env));
+}
 
-    client.register_doctor(&doctor_wallet, &name, &specialization, &cert_hash);
+#[test]
+#[should_panic(expected = "Doctor not verified")]
+fn test_unverified_doctor_cannot_add_record() {
+    let env = Env::default();
+    let (client, _doctors, _admin, doctor) = setup_with_doctor(&env);
+    let patient = Address::generate(&env);
 
-    // This should panic
-    client.verify_doctor(&doctor_wallet, &fake_institution);
+    client.grant_access(&patient, &doctor);
+    // The doctor's license lapses
+    env.ledger().set_timestamp(100_000);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &Bytes::from_array(&env, &[1, 2, 3]),
+        &String::from_str(&env, "Blood test results"),
+    );
 }
 
 #[test]
 fn test_grant_access_and_add_medical_record() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
-    let client = MedicalRegistryClient::new(&env, &contract_id);
+    let (client, _doctors, _admin, doctor) = setup_with_doctor(&env);
 
     let patient = Address::generate(&env);
-    let doctor = Address::generate(&env);
 
     let hash = Bytes::from_array(&env, &[1, 2, 3]);
     let desc = String::from_str(&env, "Blood test results");
 
-    env.mock_all_auths();
-
     client.grant_access(&patient, &doctor);
     client.add_medical_record(&patient, &doctor, &hash, &desc);
 
@@ -180,7 +229,10 @@ fn test_grant_access_and_add_medical_record() {
 #[should_panic(expected = "Doctor not authorized")]
 fn test_unauthorized_doctor_cannot_add_record() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
+    let contract_id = env.register(
+        MedicalRegistry,
+        (Address::generate(&env), Address::generate(&env)),
+    );
     let client = MedicalRegistryClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -197,13 +249,9 @@ fn test_unauthorized_doctor_cannot_add_record() {
 #[test]
 fn test_revoke_access() {
     let env = Env::default();
-    let contract_id = env.register(MedicalRegistry, ());
-    let client = MedicalRegistryClient::new(&env, &contract_id);
+    let (client, _doctors, _admin, doctor) = setup_with_doctor(&env);
 
     let patient = Address::generate(&env);
-    let doctor = Address::generate(&env);
-
-    env.mock_all_auths();
 
     client.grant_access(&patient, &doctor);
     client.revoke_access(&patient, &doctor);
@@ -163,6 +163,25 @@ impl TelemedicineContract {
         Ok(())
     }
 
+    /// Whether `visit_id` is a completed visit between this patient and provider.
+    /// Used by the doctor registry to accept reviews only from seen patients.
+    pub fn is_completed_visit(
+        env: Env,
+        visit_id: u64,
+        patient_id: Address,
+        provider_id: Address,
+    ) -> bool {
+        let visit: Option<VirtualVisit> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VirtualVisit(visit_id));
+        visit.is_some_and(|visit| {
+            visit.status == VisitStatus::Completed
+                && visit.patient_id == patient_id
+                && visit.provider_id == provider_id
+        })
+    }
+
     pub fn verify_telemedicine_eligibility(
         env: Env,
         patient_id: Address,  // Unused in this mock, but present in signature
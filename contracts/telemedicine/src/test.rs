@@ -94,6 +94,41 @@ fn test_telemedicine_lifecycle() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_is_completed_visit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, TelemedicineContract);
+    let client = TelemedicineContractClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    let visit_id = client.schedule_virtual_visit(
+        &patient_id,
+        &provider_id,
+        &1700000000,
+        &Symbol::new(&env, "Consult"),
+        &30,
+        &Symbol::new(&env, "ZoomHD"),
+        &true,
+    );
+    assert!(!client.is_completed_visit(&visit_id, &patient_id, &provider_id));
+
+    client.start_virtual_session(
+        &visit_id,
+        &provider_id,
+        &1700000010,
+        &String::from_str(&env, "NY"),
+    );
+    assert!(!client.is_completed_visit(&visit_id, &patient_id, &provider_id));
+
+    client.end_virtual_session(&visit_id, &provider_id, &1700001210, &20);
+    assert!(client.is_completed_visit(&visit_id, &patient_id, &provider_id));
+    assert!(!client.is_completed_visit(&visit_id, &provider_id, &provider_id));
+    assert!(!client.is_completed_visit(&(visit_id + 1), &patient_id, &provider_id));
+}
+
 #[test]
 fn test_auth_and_eligibility_failures() {
     let env = Env::default();